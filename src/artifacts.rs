@@ -0,0 +1,231 @@
+use crate::conditions::{format_condition_key, format_operator};
+use crate::error::{GreqError, Result};
+use crate::models::{ExecutionResult, GreqFile, Response};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Run-level summary written at the top of the `--output-dir`, read back by `--rerun-failed`
+pub const RUN_RESULTS_FILE: &str = "results.json";
+
+/// The files written into the folder of each executed file
+const ARTIFACT_FILES: [&str; 4] = ["request.http", "response.http", "headers.json", "result.json"];
+
+/// Per-file debugging artifacts written under a directory for `--output-dir`: the resolved
+/// request, the raw response, the response headers and the evaluation result of every executed file
+#[derive(Debug, Clone)]
+pub struct ArtifactWriter {
+    dir: PathBuf,
+}
+
+impl ArtifactWriter {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self { dir: dir.as_ref().to_path_buf() }
+    }
+
+    /// Folder of a `.greq` file: its path including the extension, so `users/create.greq` gets
+    /// `users/create.greq/` and never nests inside the folder of `users.greq`. Root prefixes are
+    /// dropped and `..` becomes `_up` to stay inside the directory
+    pub fn file_dir(&self, file_path: &str) -> PathBuf {
+        Path::new(file_path)
+            .components()
+            .fold(self.dir.clone(), |dir, component| match component {
+                Component::Normal(part) => dir.join(part),
+                Component::ParentDir => dir.join("_up"),
+                _ => dir,
+            })
+    }
+
+    /// Remove the artifacts of a previous run so a failed request leaves no stale response behind.
+    /// Only the files this writer creates are removed
+    pub fn clear(&self, file_path: &str) -> Result<()> {
+        let dir = self.file_dir(file_path);
+        for name in ARTIFACT_FILES {
+            match fs::remove_file(dir.join(name)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Write `request.http`: the request as sent, after placeholders and hooks were applied
+    pub fn write_request(&self, file_path: &str, greq_file: &GreqFile) -> Result<()> {
+        let dir = self.file_dir(file_path);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("request.http"), format_request(greq_file))?;
+        Ok(())
+    }
+
+    /// Write `result.json` and, when the file got a response, `response.http` and `headers.json`
+    pub fn write_result(&self, result: &ExecutionResult) -> Result<()> {
+        let dir = self.file_dir(&result.file_path);
+        fs::create_dir_all(&dir)?;
+        if let Some(response) = &result.response {
+            fs::write(dir.join("response.http"), format_response(response))?;
+            fs::write(dir.join("headers.json"), serde_json::to_string_pretty(&sorted_headers(response))? + "\n")?;
+        }
+
+        let summary = result_summary(result);
+        fs::write(dir.join("result.json"), serde_json::to_string_pretty(&summary)? + "\n")?;
+        Ok(())
+    }
+
+    /// Write `results.json` at the top of the directory: the `result.json` summary of every file
+    /// that ran. Files of an earlier run that did not run this time keep their entry, like their folder
+    pub fn write_run_results(&self, results: &[ExecutionResult]) -> Result<()> {
+        let path = self.dir.join(RUN_RESULTS_FILE);
+        let ran: Vec<&str> = results.iter().map(|result| result.file_path.as_str()).collect();
+        let mut summaries: Vec<Value> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Vec<Value>>(&text).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|summary| summary.get("file").and_then(Value::as_str).is_none_or(|file| !ran.contains(&file)))
+            .collect();
+        summaries.extend(results.iter().map(result_summary));
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, serde_json::to_string_pretty(&summaries)? + "\n")?;
+        Ok(())
+    }
+}
+
+/// The `result.json` object of one file, also used for its entry in `results.json`
+fn result_summary(result: &ExecutionResult) -> Value {
+    json!({
+        "file": result.file_path,
+        "success": result.success,
+        "status": result.response.as_ref().map(|response| response.status_code),
+        "latency_ms": result.response.as_ref().map(|response| response.latency.as_millis() as u64),
+        "attempts": result.response.as_ref().map(|response| response.attempts),
+        "failed_conditions": result.failed_conditions,
+        "error": result.error,
+        "request_id": result.request_id,
+        "conditions": result.condition_outcomes.iter().map(|outcome| json!({
+            "name": outcome.name,
+            "key": format_condition_key(&outcome.key),
+            "operator": format_operator(&outcome.operator),
+            "negated": outcome.negated,
+            "expected": outcome.expected,
+            "actual": outcome.actual,
+            "error": outcome.error,
+            "passed": outcome.passed,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// Whether each file passed, keyed by its path, from the `results.json` of a previous run
+pub fn load_run_results<P: AsRef<Path>>(path: P) -> Result<HashMap<String, bool>> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+    let summaries: Vec<Value> = serde_json::from_str(&text).map_err(|e| {
+        GreqError::Validation(format!("{} is not the {RUN_RESULTS_FILE} of a previous run: {e}", path.display()))
+    })?;
+    Ok(summaries
+        .iter()
+        .filter_map(|summary| Some((summary.get("file")?.as_str()?.to_string(), summary.get("success")?.as_bool()?)))
+        .collect())
+}
+
+/// Files to run for `--rerun-failed`, in the given order: those that failed in the previous run and
+/// those it has no result for. The second list holds the latter, to be reported
+pub fn select_failed_files(previous: &HashMap<String, bool>, files: &[PathBuf]) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut selected = Vec::new();
+    let mut without_result = Vec::new();
+    for file in files {
+        match previous.get(&file.display().to_string()) {
+            Some(true) => {},
+            Some(false) => selected.push(file.clone()),
+            None => {
+                selected.push(file.clone());
+                without_result.push(file.clone());
+            },
+        }
+    }
+    (selected, without_result)
+}
+
+/// The request line with the full URL, the headers and the body
+fn format_request(greq_file: &GreqFile) -> String {
+    let content = &greq_file.content;
+    let mut headers: Vec<(&String, &String)> = content.headers.iter().collect();
+    headers.sort();
+
+    let mut text = format!("{} {} {}\n", content.request_line.method, greq_file.request_url(), content.request_line.version);
+    for (name, value) in headers {
+        text.push_str(&format!("{name}: {value}\n"));
+    }
+    if let Some(body) = &content.body {
+        text.push('\n');
+        text.push_str(body);
+    }
+    text
+}
+
+/// The status line, the headers and the body as received
+fn format_response(response: &Response) -> String {
+    let mut text = format!("HTTP {}\n", response.status_code);
+    for (name, value) in sorted_headers(response) {
+        text.push_str(&format!("{name}: {value}\n"));
+    }
+    text.push('\n');
+    text.push_str(&response.body);
+    text
+}
+
+/// Response headers ordered by name
+fn sorted_headers(response: &Response) -> BTreeMap<&String, &String> {
+    response.headers.iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_dir_mirrors_input_path() {
+        let writer = ArtifactWriter::new("out");
+        assert_eq!(writer.file_dir("tests/users/create.greq"), Path::new("out/tests/users/create.greq"));
+        assert_eq!(writer.file_dir("/abs/users.greq"), Path::new("out/abs/users.greq"));
+        assert_eq!(writer.file_dir("../shared/login.greq"), Path::new("out/_up/shared/login.greq"));
+    }
+
+    #[test]
+    fn test_file_folders_do_not_nest() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = ArtifactWriter::new(dir.path());
+        let result = ExecutionResult::for_file;
+        writer.write_result(&result("a/b.greq", true)).unwrap();
+        writer.write_result(&result("a.greq", false)).unwrap();
+
+        // Clearing a.greq before its next run leaves the artifacts of a/b.greq alone
+        writer.clear("a.greq").unwrap();
+        assert!(!writer.file_dir("a.greq").join("result.json").exists());
+        assert!(writer.file_dir("a/b.greq").join("result.json").exists());
+        assert!(!writer.file_dir("a/b.greq").starts_with(writer.file_dir("a.greq")));
+    }
+
+    #[test]
+    fn test_select_failed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = ArtifactWriter::new(dir.path());
+        let result = ExecutionResult::for_file;
+        writer.write_run_results(&[result("users.greq", true), result("orders.greq", false)]).unwrap();
+        // A later run of one file replaces only its entry
+        writer.write_run_results(&[result("orders.greq", false)]).unwrap();
+
+        let previous = load_run_results(dir.path().join(RUN_RESULTS_FILE)).unwrap();
+        assert_eq!(previous.len(), 2);
+        let files: Vec<PathBuf> = ["users.greq", "orders.greq", "new.greq"].into_iter().map(PathBuf::from).collect();
+        let (selected, without_result) = select_failed_files(&previous, &files);
+        assert_eq!(selected, [PathBuf::from("orders.greq"), PathBuf::from("new.greq")]);
+        assert_eq!(without_result, [PathBuf::from("new.greq")]);
+
+        let error = load_run_results(dir.path()).unwrap_err();
+        assert!(matches!(error, GreqError::Io(_)), "{error}");
+        fs::write(dir.path().join("other.json"), "{}").unwrap();
+        let error = load_run_results(dir.path().join("other.json")).unwrap_err();
+        assert!(error.to_string().contains("is not the results.json of a previous run"), "{error}");
+    }
+}
@@ -0,0 +1,265 @@
+use crate::conditions::summarize_json_differences;
+use crate::error::{GreqError, Result};
+use crate::json_path::{parse_json_path, PathPart};
+use crate::models::{GreqFile, Response};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Response headers left out of snapshots because they change on every response
+const VOLATILE_HEADERS: &[&str] = &[
+    "age",
+    "cf-ray",
+    "content-length",
+    "date",
+    "etag",
+    "expires",
+    "last-modified",
+    "server-timing",
+    "set-cookie",
+    "x-request-id",
+];
+
+/// Snapshot field holding the latency in milliseconds
+const LATENCY_FIELD: &str = "latency_ms";
+
+/// Response snapshots kept in a directory for `--baseline`. The first run records one
+/// snapshot per file; later runs compare the response with it
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    dir: PathBuf,
+    /// Rewrite the snapshots instead of comparing (`--update-baseline`)
+    update: bool,
+    /// Percentage the latency may exceed the recorded one by (`--latency-regression-threshold`)
+    latency_threshold: Option<f64>,
+}
+
+/// What happened when a response was checked against its snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum BaselineOutcome {
+    /// No snapshot existed (or `--update-baseline` was given) and the response was written
+    Recorded(PathBuf),
+    Matched,
+    /// The response differs; holds the snapshot path and the differences
+    Differs(PathBuf, String),
+}
+
+impl Baseline {
+    pub fn new<P: AsRef<Path>>(dir: P, update: bool) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            update,
+            latency_threshold: None,
+        }
+    }
+
+    /// Also fail responses slower than the recorded latency by more than `percent`
+    pub fn with_latency_threshold(mut self, percent: f64) -> Self {
+        self.latency_threshold = Some(percent);
+        self
+    }
+
+    /// Snapshot file of a `.greq` file: its path with separators flattened, e.g. `users__create.json`
+    pub fn snapshot_path(&self, file_path: &str) -> PathBuf {
+        let name: Vec<String> = Path::new(file_path)
+            .with_extension("")
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        self.dir.join(format!("{}.json", name.join("__")))
+    }
+
+    /// Compare the response of a file with its snapshot, ignoring the file's `baseline-ignore` paths
+    pub fn check(&self, greq_file: &GreqFile, response: &Response) -> Result<BaselineOutcome> {
+        let path = self.snapshot_path(&greq_file.file_path);
+        let mut current = snapshot(response);
+
+        if self.update || !path.exists() {
+            fs::create_dir_all(&self.dir)?;
+            fs::write(&path, serde_json::to_string_pretty(&current)? + "\n")?;
+            return Ok(BaselineOutcome::Recorded(path));
+        }
+
+        let mut recorded: Value = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GreqError::Validation(format!("Baseline snapshot {} is not valid JSON: {}", path.display(), e))
+        })?;
+        let recorded_latency = recorded[LATENCY_FIELD].clone();
+        for ignored in greq_file.header.baseline_ignore.iter().map(String::as_str).chain([LATENCY_FIELD]) {
+            remove_path(&mut current, ignored);
+            remove_path(&mut recorded, ignored);
+        }
+
+        let mut differences = summarize_json_differences(&current, &recorded);
+        if let Some(regression) = self.latency_regression(response, &recorded_latency) {
+            differences.push(regression);
+        }
+        if differences.is_empty() {
+            Ok(BaselineOutcome::Matched)
+        } else {
+            Ok(BaselineOutcome::Differs(path, differences.join("; ")))
+        }
+    }
+
+    /// Describe a latency above the recorded one by more than the threshold. Snapshots recorded
+    /// before latencies were stored have no latency and are never reported
+    fn latency_regression(&self, response: &Response, recorded_latency: &Value) -> Option<String> {
+        let threshold = self.latency_threshold?;
+        let recorded_ms = recorded_latency.as_f64()?;
+        let current_ms = response.latency.as_millis() as f64;
+        // A 0ms recording would make any latency an infinite regression
+        let allowed_ms = recorded_ms.max(1.0) * (1.0 + threshold / 100.0);
+        (current_ms > allowed_ms).then(|| format!(
+            "latency {}ms is {:.0}% above the recorded {}ms (threshold {}%)",
+            current_ms, (current_ms / recorded_ms.max(1.0) - 1.0) * 100.0, recorded_ms, threshold
+        ))
+    }
+}
+
+/// The recorded form of a response: status, stable headers, the body (as JSON when it parses)
+/// and the latency, which is only compared with `--latency-regression-threshold`
+fn snapshot(response: &Response) -> Value {
+    let mut headers: Vec<(&String, &String)> = response
+        .headers
+        .iter()
+        .filter(|(name, _)| !VOLATILE_HEADERS.contains(&name.as_str()))
+        .collect();
+    headers.sort();
+    let headers: Map<String, Value> = headers
+        .into_iter()
+        .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+        .collect();
+    let body = response.json().cloned().unwrap_or_else(|| Value::String(response.body.clone()));
+
+    json!({ "status": response.status_code, "headers": headers, "body": body, LATENCY_FIELD: response.latency.as_millis() as u64 })
+}
+
+/// Remove the value at a path such as `body.items[0].updated_at`; array elements become null
+/// so the indexes of the following elements do not shift
+fn remove_path(value: &mut Value, path: &str) {
+    let Ok(mut parts) = parse_json_path(path) else {
+        return;
+    };
+    let Some(last) = parts.pop() else {
+        return;
+    };
+
+    let mut current = value;
+    for part in parts {
+        let next = match (part, current) {
+            (PathPart::Property(key), Value::Object(object)) => object.get_mut(&key),
+            (PathPart::Index(index), Value::Array(array)) => array.get_mut(index),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+
+    match (last, current) {
+        (PathPart::Property(key), Value::Object(object)) => {
+            object.remove(&key);
+        }
+        (PathPart::Index(index), Value::Array(array)) => {
+            if let Some(element) = array.get_mut(index) {
+                *element = Value::Null;
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Content, Footer, Header};
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn create_response(body: &str) -> Response {
+        Response {
+            status_code: 200,
+            headers: HashMap::from([
+                ("content-type".to_string(), "application/json".to_string()),
+                ("date".to_string(), "Tue, 01 Jul 2025 10:00:00 GMT".to_string()),
+            ]),
+            body: body.to_string(),
+            ..Response::default()
+        }
+    }
+
+    #[test]
+    fn test_records_then_compares() {
+        let dir = tempdir().unwrap();
+        let baseline = Baseline::new(dir.path().join("snapshots"), false);
+        let mut greq_file = GreqFile {
+            header: Header::default(),
+            content: Content::default(),
+            footer: Footer::default(),
+            file_path: "./api/users.greq".to_string(),
+        };
+
+        let recorded = baseline.check(&greq_file, &create_response(r#"{"id": 1, "updated_at": "10:00"}"#)).unwrap();
+        let path = dir.path().join("snapshots").join("api__users.json");
+        assert_eq!(recorded, BaselineOutcome::Recorded(path.clone()));
+        assert!(!fs::read_to_string(&path).unwrap().contains("\"date\""));
+
+        let changed = create_response(r#"{"id": 2, "updated_at": "11:00"}"#);
+        let outcome = baseline.check(&greq_file, &changed).unwrap();
+        assert_eq!(
+            outcome,
+            BaselineOutcome::Differs(path.clone(), "$.body.id: 2 instead of 1; $.body.updated_at: \"11:00\" instead of \"10:00\"".to_string())
+        );
+
+        greq_file.header = Header {
+            baseline_ignore: vec!["body.id".to_string(), "body.updated_at".to_string()],
+            ..Header::default()
+        };
+        assert_eq!(baseline.check(&greq_file, &changed).unwrap(), BaselineOutcome::Matched);
+
+        let updated = Baseline::new(dir.path().join("snapshots"), true).check(&greq_file, &changed).unwrap();
+        assert_eq!(updated, BaselineOutcome::Recorded(path.clone()));
+        assert!(fs::read_to_string(&path).unwrap().contains("11:00"));
+    }
+
+    #[test]
+    fn test_latency_regression() {
+        let dir = tempdir().unwrap();
+        let greq_file = GreqFile {
+            header: Header::default(),
+            content: Content::default(),
+            footer: Footer::default(),
+            file_path: "orders.greq".to_string(),
+        };
+        let with_latency = |millis: u64| Response {
+            latency: Duration::from_millis(millis),
+            ..create_response("{}")
+        };
+        let baseline = Baseline::new(dir.path(), false).with_latency_threshold(20.0);
+
+        assert!(matches!(baseline.check(&greq_file, &with_latency(100)).unwrap(), BaselineOutcome::Recorded(_)));
+        assert_eq!(baseline.check(&greq_file, &with_latency(120)).unwrap(), BaselineOutcome::Matched);
+        let BaselineOutcome::Differs(_, differences) = baseline.check(&greq_file, &with_latency(150)).unwrap() else {
+            panic!("a 50% slower response should fail");
+        };
+        assert_eq!(differences, "latency 150ms is 50% above the recorded 100ms (threshold 20%)");
+
+        // Without a threshold the latency is recorded but never compared
+        assert_eq!(Baseline::new(dir.path(), false).check(&greq_file, &with_latency(900)).unwrap(), BaselineOutcome::Matched);
+    }
+
+    #[test]
+    fn test_remove_path() {
+        let mut value = json!({"body": {"items": [{"id": 1, "at": "x"}, {"id": 2}]}});
+
+        remove_path(&mut value, "body.items[0].at");
+        remove_path(&mut value, "body.items[1]");
+        remove_path(&mut value, "body.missing.path");
+
+        assert_eq!(value, json!({"body": {"items": [{"id": 1}, null]}}));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::executor::referenced_files;
+use crate::models::ExecutionOptions;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files that differ from `base_ref` in the git repository containing `dir`, including
+/// uncommitted and untracked files. `None` outside a git repository or when git fails
+pub fn git_changed_files(base_ref: &str, dir: &Path) -> Option<HashSet<PathBuf>> {
+    let git = |args: &[&str], dir: &Path| -> Option<String> {
+        let output = Command::new("git").args(args).current_dir(dir).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let root = PathBuf::from(git(&["rev-parse", "--show-toplevel"], dir)?.trim());
+    let changed = git(&["diff", "--name-only", base_ref, "--"], &root)?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"], &root)?;
+
+    Some(
+        changed
+            .lines()
+            .chain(untracked.lines())
+            .filter(|line| !line.is_empty())
+            .map(|line| canonical(&root.join(line)))
+            .collect(),
+    )
+}
+
+/// The files that changed or that use a changed file through `depends-on` or `extends`.
+/// Files whose references cannot be resolved are kept so their errors are reported
+pub fn select_changed_files(files: &[PathBuf], changed: &HashSet<PathBuf>, options: &ExecutionOptions) -> Vec<PathBuf> {
+    files
+        .iter()
+        .filter(|file| match referenced_files(file, options) {
+            Ok(references) => references.iter().any(|reference| changed.contains(&canonical(reference))),
+            Err(e) => {
+                log::warn!("⚠ Cannot resolve the references of {}, running it: {}", file.display(), e);
+                true
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_files_referencing_changed_files_are_selected() {
+        let dir = tempdir().unwrap();
+        let write = |name: &str, header: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, format!("{header}\n====\nGET /\nhost: example.com")).unwrap();
+            path
+        };
+        let base = write("base.greq", "project: shop");
+        let login = write("login.greq", "extends: base.greq");
+        let orders = write("orders.greq", "depends-on: login.greq");
+        let health = write("health.greq", "project: shop");
+        let files = vec![orders.clone(), health.clone(), login.clone()];
+
+        let changed = HashSet::from([canonical(&base)]);
+        assert_eq!(select_changed_files(&files, &changed, &ExecutionOptions::default()), vec![orders.clone(), login.clone()]);
+
+        let changed = HashSet::from([canonical(&health)]);
+        assert_eq!(select_changed_files(&files, &changed, &ExecutionOptions::default()), vec![health]);
+    }
+
+    #[test]
+    fn test_git_changed_files() {
+        let dir = tempdir().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=greq", "-c", "user.email=greq@example.com"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        };
+        assert!(git(&["init", "-q"]), "git must be installed to run this test");
+        fs::write(dir.path().join("users.greq"), "v1").unwrap();
+        fs::write(dir.path().join("orders.greq"), "v1").unwrap();
+        assert!(git(&["add", "."]) && git(&["commit", "-q", "-m", "initial"]));
+        fs::write(dir.path().join("users.greq"), "v2").unwrap();
+        fs::write(dir.path().join("new.greq"), "v1").unwrap();
+
+        let changed = git_changed_files("HEAD", dir.path()).unwrap();
+
+        assert_eq!(changed, HashSet::from([canonical(&dir.path().join("users.greq")), canonical(&dir.path().join("new.greq"))]));
+        assert!(git_changed_files("HEAD", &std::env::temp_dir().join("greq-no-such-dir")).is_none());
+    }
+}
@@ -1,17 +1,38 @@
-use crate::models::{Condition, ConditionKey, Operator, Response};
+use crate::models::{
+    BodyTransform, Condition, ConditionKey, ConditionOutcome, Header, Operator, PeerCertificate, Quantifier, Response, ResponseTimings,
+    TransformKind,
+};
+use chrono::Utc;
 use crate::error::{GreqError, Result};
+use crate::parser::resolve_relative_path;
+use crate::json_path::{expand_wildcard_path, get_path, json_type_name, json_value_to_string, navigate_json_path, navigate_json_path_mut};
+use base64::Engine;
 use regex::Regex;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
 
 /// Evaluate all conditions against a response
 /// Evaluate conditions against a response with file context for better error reporting
 /// Stops at the first failing condition to preserve evaluation order
 pub fn evaluate_conditions(conditions: &[Condition], response: &Response, file_path: &str) -> Result<Vec<String>> {
+    evaluate_conditions_with_header(conditions, response, file_path, &Header::default())
+}
+
+/// Evaluate conditions using header properties that affect how keys are resolved (e.g. `error-envelope-path`)
+pub fn evaluate_conditions_with_header(conditions: &[Condition], response: &Response, file_path: &str, header: &Header) -> Result<Vec<String>> {
     let condition_groups = group_conditions(conditions);
     
-    for group in condition_groups {
-        if let Some(failed_desc) = evaluate_condition_group_with_details(&group, response, file_path)? {
+    for (index, group) in condition_groups.iter().enumerate() {
+        if let Some(failed_desc) = evaluate_condition_group_with_details(group, response, file_path, header)? {
             // Return immediately on first failure to preserve order
+            let failed_desc = match describe_and_group(&condition_groups, index) {
+                Some(context) => format!("{failed_desc} ({context})"),
+                None => failed_desc,
+            };
             return Ok(vec![failed_desc]);
         }
     }
@@ -19,6 +40,236 @@ pub fn evaluate_conditions(conditions: &[Condition], response: &Response, file_p
     Ok(Vec::new())
 }
 
+/// The outcome of one condition in an `--explain` report
+#[derive(Debug, Clone)]
+pub struct ConditionExplanation {
+    pub condition: Condition,
+    pub passed: bool,
+    /// The value the condition saw, or why it could not be evaluated
+    pub actual: std::result::Result<String, String>,
+}
+
+/// Evaluate every condition without stopping at the first failure, for `--explain`.
+/// Conditions of an `or` group are reported individually; a condition that cannot be
+/// evaluated (e.g. a missing JSON path) is reported as failed with the reason
+pub fn explain_conditions(conditions: &[Condition], response: &Response, file_path: &str, header: &Header) -> Vec<ConditionExplanation> {
+    evaluate_and_explain_conditions(conditions, response, file_path, header).0
+}
+
+/// Evaluate every condition once, returning the explanation of each and the failure messages
+/// `evaluate_conditions_with_header` reports for them: the first failing `or` group, or the
+/// error of the condition it would have stopped at
+pub fn evaluate_and_explain_conditions(
+    conditions: &[Condition],
+    response: &Response,
+    file_path: &str,
+    header: &Header,
+) -> (Vec<ConditionExplanation>, Result<Vec<String>>) {
+    let results: Vec<Result<ConditionResult>> = conditions
+        .iter()
+        .map(|condition| evaluate_single_condition_with_details(condition, response, file_path, header))
+        .collect();
+    let failure = first_failed_group(&group_conditions(conditions), &results);
+
+    let mut error = None;
+    let explanations = conditions
+        .iter()
+        .zip(results)
+        .enumerate()
+        .map(|(index, (condition, result))| {
+            let (passed, actual) = match result {
+                Ok(ConditionResult::Passed { actual_value }) => (true, Ok(actual_value)),
+                Ok(ConditionResult::Failed { actual_value, .. }) => (false, Ok(actual_value)),
+                Err(e) => {
+                    let message = e.to_string();
+                    if failure == Err(index) {
+                        error = Some(e);
+                    }
+                    (false, Err(message))
+                }
+            };
+            ConditionExplanation { condition: condition.clone(), passed, actual }
+        })
+        .collect();
+
+    let failed_conditions = match (failure, error) {
+        (Ok(failed_conditions), _) => Ok(failed_conditions),
+        (Err(_), Some(e)) => Err(e),
+        (Err(index), None) => unreachable!("condition {index} has no error"),
+    };
+    (explanations, failed_conditions)
+}
+
+/// The failure message of the first failing group, as `evaluate_conditions_with_header` words it,
+/// or the index of the condition whose error it would return
+fn first_failed_group(groups: &[Vec<&Condition>], results: &[Result<ConditionResult>]) -> std::result::Result<Vec<String>, usize> {
+    let mut index = 0;
+    for (group_index, group) in groups.iter().enumerate() {
+        let mut first_failure = None;
+        let mut passed = false;
+        for offset in 0..group.len() {
+            match &results[index + offset] {
+                Err(_) => return Err(index + offset),
+                Ok(ConditionResult::Passed { .. }) => {
+                    passed = true;
+                    break;
+                }
+                Ok(ConditionResult::Failed { actual_value, condition }) => {
+                    first_failure.get_or_insert_with(|| format_failed_condition_with_actual(condition, actual_value));
+                }
+            }
+        }
+        if let (false, Some(failed_desc)) = (passed, first_failure) {
+            let failed_desc = match describe_and_group(groups, group_index) {
+                Some(context) => format!("{failed_desc} ({context})"),
+                None => failed_desc,
+            };
+            return Ok(vec![failed_desc]);
+        }
+        index += group.len();
+    }
+    Ok(Vec::new())
+}
+
+impl From<&ConditionExplanation> for ConditionOutcome {
+    fn from(explanation: &ConditionExplanation) -> Self {
+        let condition = &explanation.condition;
+        Self {
+            key: condition.key.clone(),
+            operator: condition.operator.clone(),
+            negated: condition.is_not,
+            expected: condition.value.clone(),
+            actual: explanation.actual.as_ref().ok().cloned(),
+            error: explanation.actual.as_ref().err().cloned(),
+            passed: explanation.passed,
+            name: condition.name.clone(),
+        }
+    }
+}
+
+/// Render one line of an `--explain` report, e.g. `PASS status-code equals '200' (actual: '200')`
+pub fn format_explanation(explanation: &ConditionExplanation) -> String {
+    let verdict = if explanation.passed { "PASS" } else { "FAIL" };
+    let prefix = if explanation.condition.is_or {
+        "or "
+    } else if explanation.condition.is_and {
+        "and "
+    } else {
+        ""
+    };
+    match &explanation.actual {
+        Ok(actual) => format!("{} {}{} (actual: '{}')", verdict, prefix, format_condition(&explanation.condition), actual),
+        Err(error) => format!("{} {}{} (error: {})", verdict, prefix, format_condition(&explanation.condition), error),
+    }
+}
+
+/// Describe the `and` group that the OR group at `index` belongs to, e.g.
+/// `condition 2 of 3 in and group: status-code equals '200' and ...`. `None` outside an `and` group
+fn describe_and_group(groups: &[Vec<&Condition>], index: usize) -> Option<String> {
+    let start = (0..=index).rev().find(|&i| !groups[i][0].is_and)?;
+    let end = (index + 1..groups.len()).find(|&i| !groups[i][0].is_and).unwrap_or(groups.len());
+    if end - start < 2 {
+        return None;
+    }
+    
+    let members: Vec<String> = groups[start..end]
+        .iter()
+        .map(|group| group.iter().map(|condition| format_condition(condition)).collect::<Vec<_>>().join(" or "))
+        .collect();
+    Some(format!("condition {} of {} in and group: {}", index - start + 1, members.len(), members.join(" and ")))
+}
+
+/// Apply the footer's `transform` lines, in order, to a copy of the response body.
+/// The response is borrowed unchanged when there are no transforms
+pub fn apply_body_transforms<'a>(response: &'a Response, transforms: &[BodyTransform], file_path: &str) -> Result<Cow<'a, Response>> {
+    if transforms.is_empty() {
+        return Ok(Cow::Borrowed(response));
+    }
+    
+    let mut body = response.body.clone();
+    for transform in transforms {
+        body = apply_body_transform(&body, transform)
+            .map_err(|e| GreqError::ConditionFailed(format!("{}: transform {}: {}", file_path, format_transform_target(transform), e)))?;
+    }
+    
+    Ok(Cow::Owned(Response {
+        body,
+        json_cache: OnceLock::new(),
+        ..response.clone()
+    }))
+}
+
+/// Return the body with the value at the transform path replaced by its decoded form
+fn apply_body_transform(body: &str, transform: &BodyTransform) -> std::result::Result<String, String> {
+    if transform.path.is_empty() {
+        // A body that is a JSON string literal is decoded from its contents
+        let text = match serde_json::from_str::<Value>(body) {
+            Ok(Value::String(text)) => text,
+            _ => body.to_string(),
+        };
+        return match decode_transform_value(&text, transform.kind)? {
+            Value::String(decoded) => Ok(decoded),
+            value => Ok(value.to_string()),
+        };
+    }
+    
+    let mut root: Value = serde_json::from_str(body).map_err(|_| "response body is not valid JSON".to_string())?;
+    let target = navigate_json_path_mut(&mut root, &transform.path).map_err(|e| e.to_string())?;
+    let Value::String(text) = target else {
+        return Err(format!("expected a string, found {}", json_type_name(target)));
+    };
+    *target = decode_transform_value(text, transform.kind)?;
+    
+    Ok(root.to_string())
+}
+
+fn decode_transform_value(text: &str, kind: TransformKind) -> std::result::Result<Value, String> {
+    match kind {
+        TransformKind::Base64Decode => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .or_else(|_| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(text.trim().trim_end_matches('=')))
+                .map_err(|e| format!("invalid base64: {e}"))?;
+            String::from_utf8(bytes).map(Value::String).map_err(|_| "decoded base64 is not UTF-8 text".to_string())
+        },
+        TransformKind::UrlDecode => percent_encoding::percent_decode_str(text)
+            .decode_utf8()
+            .map(|decoded| Value::String(decoded.into_owned()))
+            .map_err(|_| "decoded value is not UTF-8 text".to_string()),
+        TransformKind::JsonParse => serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}")),
+    }
+}
+
+fn format_transform_target(transform: &BodyTransform) -> String {
+    if transform.path.is_empty() {
+        "response-body".to_string()
+    } else {
+        format!("response-body.{}", transform.path)
+    }
+}
+
+/// Resolve the `print:` keys of a footer into `file: key = value` lines, in footer order.
+/// Values that cannot be resolved are reported in place and never fail the request
+pub fn format_print_values(keys: &[ConditionKey], response: &Response, file_path: &str, header: &Header) -> Vec<String> {
+    keys.iter()
+        .map(|key| {
+            let value = extract_key_value(key, response, file_path, header).unwrap_or_else(|e| format!("<{}>", e));
+            format!("{}: {} = {}", file_path, format_condition_key(key), value)
+        })
+        .collect()
+}
+
+/// The value of a key as `print:` and `extract` see it; quantified keys give all the element values
+pub fn extract_key_value(key: &ConditionKey, response: &Response, file_path: &str, header: &Header) -> Result<String> {
+    match key {
+        ConditionKey::ResponseBodyElements { path, element_path, .. } => {
+            extract_array_element_values(response, path, element_path, file_path)
+                .map(|values| format_array_element_values(&values))
+        },
+        _ => extract_condition_value(key, response, file_path, header),
+    }
+}
+
 /// Group conditions by OR relationships
 fn group_conditions(conditions: &[Condition]) -> Vec<Vec<&Condition>> {
     let mut groups = Vec::new();
@@ -44,12 +295,12 @@ fn group_conditions(conditions: &[Condition]) -> Vec<Vec<&Condition>> {
 
 /// Evaluate a group of conditions (connected by OR) and return failure details if any
 /// Shows error from the first condition in the group if all conditions fail
-fn evaluate_condition_group_with_details(group: &[&Condition], response: &Response, file_path: &str) -> Result<Option<String>> {
+fn evaluate_condition_group_with_details(group: &[&Condition], response: &Response, file_path: &str, header: &Header) -> Result<Option<String>> {
     let mut first_failure: Option<String> = None;
     
     for condition in group {
-        match evaluate_single_condition_with_details(condition, response, file_path)? {
-            ConditionResult::Passed => return Ok(None), // If any condition passes in OR group, group passes
+        match evaluate_single_condition_with_details(condition, response, file_path, header)? {
+            ConditionResult::Passed { .. } => return Ok(None), // If any condition passes in OR group, group passes
             ConditionResult::Failed { actual_value, condition } => {
                 // Store only the first failure
                 if first_failure.is_none() {
@@ -65,19 +316,12 @@ fn evaluate_condition_group_with_details(group: &[&Condition], response: &Respon
 
 #[derive(Debug)]
 enum ConditionResult {
-    Passed,
+    Passed { actual_value: String },
     Failed { actual_value: String, condition: Condition },
 }
 
 /// Evaluate a single condition with detailed results
-fn evaluate_single_condition_with_details(condition: &Condition, response: &Response, file_path: &str) -> Result<ConditionResult> {
-    // For exists operator, we need to handle missing JSON paths gracefully
-    let actual_value = if condition.operator == Operator::Exists {
-        extract_condition_value_for_exists(&condition.key, response, file_path)
-    } else {
-        extract_condition_value(&condition.key, response, file_path)?
-    };
-    
+fn evaluate_single_condition_with_details(condition: &Condition, response: &Response, file_path: &str, header: &Header) -> Result<ConditionResult> {
     let expected_value = &condition.value;
     
     log::debug!(
@@ -88,17 +332,29 @@ fn evaluate_single_condition_with_details(condition: &Condition, response: &Resp
         condition.case_sensitive
     );
     
-    let result = match condition.operator {
-        Operator::Equals => compare_equals(&actual_value, expected_value, condition.case_sensitive),
-        Operator::Contains => compare_contains(&actual_value, expected_value, condition.case_sensitive),
-        Operator::MatchesRegex => compare_regex(&actual_value, expected_value)?,
-        Operator::LessThan => compare_numeric(&actual_value, expected_value, file_path, |a, b| a < b)?,
-        Operator::LessThanOrEqual => compare_numeric(&actual_value, expected_value, file_path, |a, b| a <= b)?,
-        Operator::GreaterThan => compare_numeric(&actual_value, expected_value, file_path, |a, b| a > b)?,
-        Operator::GreaterThanOrEqual => compare_numeric(&actual_value, expected_value, file_path, |a, b| a >= b)?,
-        Operator::StartsWith => compare_starts_with(&actual_value, expected_value, condition.case_sensitive),
-        Operator::EndsWith => compare_ends_with(&actual_value, expected_value, condition.case_sensitive),
-        Operator::Exists => compare_exists(&actual_value, expected_value, file_path)?,
+    let (result, actual_value) = if let ConditionKey::ResponseBodyElements { path, quantifier, element_path } = &condition.key {
+        evaluate_array_elements(condition, path, *quantifier, element_path, response, file_path)?
+    } else if condition.operator == Operator::EqualsBinaryFile {
+        evaluate_binary_file(condition, response, file_path, header)?
+    } else if let Some(path) = wildcard_path(&condition.key) {
+        evaluate_wildcard_exists(condition, path, response, file_path)?
+    } else if condition.operator == Operator::TypeEquals {
+        evaluate_type_equals(condition, response, file_path)?
+    } else {
+        // For exists operator, we need to handle missing JSON paths gracefully
+        let actual_value = if condition.operator == Operator::Exists {
+            extract_condition_value_for_exists(&condition.key, response, file_path, header)?
+        } else {
+            extract_condition_value(&condition.key, response, file_path, header)?
+        };
+        let expected_value = match &condition.key {
+            ConditionKey::Header(name) if is_ordering_operator(&condition.operator) => {
+                numeric_header_expectation(name, &actual_value, expected_value, file_path)?
+            },
+            _ => expected_value.clone(),
+        };
+        let result = compare_values(&condition.operator, &actual_value, &expected_value, condition.case_sensitive, file_path)?;
+        (result, actual_value)
     };
     
     let final_result = if condition.is_not { !result } else { result };
@@ -108,8 +364,44 @@ fn evaluate_single_condition_with_details(condition: &Condition, response: &Resp
     );
     
     if final_result {
-        Ok(ConditionResult::Passed)
+        Ok(ConditionResult::Passed { actual_value })
     } else {
+        // A bare count is hard to act on, so list which headers were received
+        let actual_value = if matches!(condition.key, ConditionKey::HeaderCount) {
+            let mut names: Vec<&str> = response.headers.keys().map(String::as_str).collect();
+            names.sort_unstable();
+            format!("{actual_value}; present: {}", names.join(", "))
+        } else if let ConditionKey::HeaderOccurrences(name) = &condition.key {
+            match header_occurrences(response, name) {
+                values if values.is_empty() => actual_value,
+                values => format!("{actual_value}; values: {}", values.join(" | ")),
+            }
+        } else if condition.operator == Operator::JsonEquals && !condition.is_not {
+            format!("{actual_value}; differences: {}", describe_json_differences(&actual_value, expected_value))
+        } else if condition.operator == Operator::JsonContains && !condition.is_not {
+            match first_json_contains_difference(&actual_value, expected_value, file_path)? {
+                Some(difference) => format!("{actual_value}; first difference: {difference}"),
+                None => actual_value,
+            }
+        } else if condition.operator == Operator::JsonEqualsFile && !condition.is_not {
+            let fixture = read_fixture(expected_value, file_path)?;
+            format!("{actual_value}; differences: {}", describe_json_differences(&actual_value, &fixture))
+        } else if condition.operator == Operator::ContainsAny && condition.is_not {
+            match first_listed_match(&actual_value, expected_value, condition.case_sensitive, file_path)? {
+                Some(found) => format!("{actual_value}; found '{found}'"),
+                None => actual_value,
+            }
+        } else if condition.operator == Operator::EqualsFile && !condition.is_not {
+            let fixture = read_fixture(expected_value, file_path)?;
+            format!("{actual_value}; {}", describe_first_line_difference(&actual_value, &fixture))
+        } else if let (false, Some(mismatch)) = (
+            condition.is_not,
+            sequence_mismatch(&condition.operator, &actual_value, expected_value, condition.case_sensitive, file_path)?,
+        ) {
+            format!("{actual_value}; {mismatch}")
+        } else {
+            actual_value
+        };
         Ok(ConditionResult::Failed { 
             actual_value, 
             condition: condition.clone(),
@@ -117,8 +409,162 @@ fn evaluate_single_condition_with_details(condition: &Condition, response: &Resp
     }
 }
 
+/// Compare an actual value with the expected value using the condition operator
+fn compare_values(operator: &Operator, actual_value: &str, expected_value: &str, case_sensitive: bool, file_path: &str) -> Result<bool> {
+    Ok(match operator {
+        Operator::Equals => compare_equals(actual_value, expected_value, case_sensitive),
+        Operator::Contains => compare_contains(actual_value, expected_value, case_sensitive),
+        Operator::ContainsAny => first_listed_match(actual_value, expected_value, case_sensitive, file_path)?.is_some(),
+        Operator::MatchesRegex => compare_regex(actual_value, expected_value)?,
+        Operator::LessThan => compare_numeric(actual_value, expected_value, file_path, Ordering::is_lt)?,
+        Operator::LessThanOrEqual => compare_numeric(actual_value, expected_value, file_path, Ordering::is_le)?,
+        Operator::GreaterThan => compare_numeric(actual_value, expected_value, file_path, Ordering::is_gt)?,
+        Operator::GreaterThanOrEqual => compare_numeric(actual_value, expected_value, file_path, Ordering::is_ge)?,
+        Operator::StartsWith => compare_starts_with(actual_value, expected_value, case_sensitive),
+        Operator::EndsWith => compare_ends_with(actual_value, expected_value, case_sensitive),
+        Operator::Within => compare_within(actual_value, expected_value, file_path)?,
+        Operator::ApproxEquals => compare_approx_equals(actual_value, expected_value, file_path)?,
+        Operator::JsonEquals => compare_json_equals(actual_value, expected_value, file_path)?,
+        Operator::JsonContains => first_json_contains_difference(actual_value, expected_value, file_path)?.is_none(),
+        Operator::EqualsFile => read_fixture(expected_value, file_path)?.trim_end() == actual_value.trim_end(),
+        Operator::JsonEqualsFile => compare_json_equals(actual_value, &read_fixture(expected_value, file_path)?, file_path)?,
+        Operator::EqualsBinaryFile => read_binary_fixture(expected_value, file_path)? == actual_value.as_bytes(),
+        // Text values have lost their JSON type; response-body keys are checked by `evaluate_type_equals`
+        Operator::TypeEquals => return Err(GreqError::ConditionFailed(format!(
+            "{}: type-equals only works on response-body and response-body.<path> keys", file_path
+        ))),
+        Operator::SortedAscending | Operator::SortedDescending | Operator::EqualsSequence => {
+            sequence_mismatch(operator, actual_value, expected_value, case_sensitive, file_path)?.is_none()
+        },
+        Operator::Exists => compare_exists(actual_value, expected_value, file_path)?,
+    })
+}
+
+fn is_ordering_operator(operator: &Operator) -> bool {
+    matches!(operator, Operator::LessThan | Operator::LessThanOrEqual | Operator::GreaterThan | Operator::GreaterThanOrEqual)
+}
+
+/// Check that a header compared with an ordering operator is numeric, and resolve size units
+/// (`1KB`, `2.5MB`) in the expected value of `content-length`
+fn numeric_header_expectation(name: &str, actual: &str, expected: &str, file_path: &str) -> Result<String> {
+    if actual.trim().parse::<f64>().is_err() {
+        return Err(GreqError::ConditionFailed(format!(
+            "{}: Header '{}' has the non-numeric value '{}' and cannot be compared as a number", file_path, name, actual
+        )));
+    }
+    
+    if name == "content-length" {
+        if let Some(bytes) = parse_byte_size(expected) {
+            return Ok(bytes.to_string());
+        }
+    }
+    Ok(expected.to_string())
+}
+
+/// Parse a size such as `512`, `512B`, `1KB`, `1.5MB` or `2GB` into bytes (1KB = 1024 bytes)
+fn parse_byte_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let number_end = size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len());
+    let (number, unit) = size.split_at(number_end);
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    let number: f64 = number.parse().ok()?;
+    
+    Some((number * multiplier as f64).round() as u64)
+}
+
+/// Check the elements of a JSON array against the condition, requiring any or all of them to match.
+/// Elements without the element path only satisfy `exists: false`.
+/// An empty array satisfies `all` and fails `any`. Returns the result and the element values for reporting
+fn evaluate_array_elements(
+    condition: &Condition,
+    path: &str,
+    quantifier: Quantifier,
+    element_path: &str,
+    response: &Response,
+    file_path: &str,
+) -> Result<(bool, String)> {
+    let values = extract_array_element_values(response, path, element_path, file_path)?;
+    
+    let mut matches = Vec::with_capacity(values.len());
+    for value in &values {
+        let matched = match value {
+            Some(actual) => compare_values(&condition.operator, actual, &condition.value, condition.case_sensitive, file_path)?,
+            None if condition.operator == Operator::Exists => compare_exists("", &condition.value, file_path)?,
+            None => false,
+        };
+        matches.push(matched);
+    }
+    
+    let result = match quantifier {
+        Quantifier::Any => matches.iter().any(|&matched| matched),
+        Quantifier::All => matches.iter().all(|&matched| matched),
+    };
+    Ok((result, format_array_element_values(&values)))
+}
+
+/// The JSON path of a `response-body.items[*].id` key, when it sweeps an array with `[*]`
+fn wildcard_path(key: &ConditionKey) -> Option<&str> {
+    match key {
+        ConditionKey::ResponseBodyPath(path) if path.contains("[*]") => Some(path),
+        _ => None,
+    }
+}
+
+/// Sweep a `[*]` path for `exists`: `true` needs the path in every element, `false` in none.
+/// The actual value names the first element that breaks the expectation
+fn evaluate_wildcard_exists(condition: &Condition, path: &str, response: &Response, file_path: &str) -> Result<(bool, String)> {
+    let expected_exists: bool = condition.value.parse()
+        .map_err(|_| GreqError::ConditionFailed(format!("{}: Invalid boolean value for exists: {}", file_path, condition.value)))?;
+    let value = response.json()
+        .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Response body is not valid JSON", file_path)))?;
+    let locations = expand_wildcard_path(value, path)
+        .map_err(|e| GreqError::ConditionFailed(format!("{}: {}", file_path, e)))?;
+    
+    let offending = locations.iter().find(|(_, exists)| *exists != expected_exists);
+    let actual_value = match offending {
+        Some((location, true)) => format!("response-body.{location} exists"),
+        Some((location, false)) => format!("response-body.{location} is missing"),
+        None if expected_exists => format!("present in all {} elements", locations.len()),
+        None => format!("missing in all {} elements", locations.len()),
+    };
+    Ok((offending.is_none(), actual_value))
+}
+
+/// Resolve `element_path` in every element of the array at `path`; `None` marks elements without it
+fn extract_array_element_values(response: &Response, path: &str, element_path: &str, file_path: &str) -> Result<Vec<Option<String>>> {
+    let value = response.json()
+        .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Response body is not valid JSON", file_path)))?;
+    let array = navigate_json_path(value, path)
+        .map_err(|e| GreqError::ConditionFailed(format!("{}: {}", file_path, e)))?;
+    let Value::Array(elements) = array else {
+        let location = if path.is_empty() { "response-body".to_string() } else { format!("response-body.{path}") };
+        return Err(GreqError::ConditionFailed(format!(
+            "{}: '{}' is {}, expected an array for any/all", file_path, location, json_type_name(array)
+        )));
+    };
+    
+    Ok(elements
+        .iter()
+        .map(|element| get_path(element, element_path).map(|value| json_value_to_string(&value)))
+        .collect())
+}
+
+fn format_array_element_values(values: &[Option<String>]) -> String {
+    let values: Vec<String> = values
+        .iter()
+        .map(|value| value.clone().unwrap_or_else(|| "<missing>".to_string()))
+        .collect();
+    format!("[{}]", values.join(", "))
+}
+
 /// Extract the actual value for a condition key from the response
-fn extract_condition_value(key: &ConditionKey, response: &Response, file_path: &str) -> Result<String> {
+fn extract_condition_value(key: &ConditionKey, response: &Response, file_path: &str, header: &Header) -> Result<String> {
     match key {
         ConditionKey::StatusCode => Ok(response.status_code.to_string()),
         ConditionKey::Latency => Ok(response.latency.as_millis().to_string()),
@@ -134,16 +580,78 @@ fn extract_condition_value(key: &ConditionKey, response: &Response, file_path: &
         ConditionKey::Header(header_name) => {
             Ok(response.headers.get(&header_name.to_lowercase()).cloned().unwrap_or_default())
         },
+        ConditionKey::Trailer(name) => {
+            let trailers = response.trailers.as_ref()
+                .ok_or_else(|| GreqError::ConditionFailed(format!(
+                    "{}: trailers.{} is unavailable, the response had no trailer section (trailers need HTTP/2 or a chunked HTTP/1.1 response)",
+                    file_path, name
+                )))?;
+            Ok(trailers.get(name).cloned().unwrap_or_default())
+        },
         ConditionKey::ResponseBodyPath(path) => {
-            extract_json_path_value(&response.body, path, file_path)
+            extract_json_path_value(response, path, file_path)
+        },
+        ConditionKey::Cookie(cookie_path) => Ok(extract_cookie_value(response, cookie_path)),
+        ConditionKey::LocationQuery(param) => Ok(extract_location_query_param(response, param)),
+        ConditionKey::ErrorCode | ConditionKey::ErrorMessage => {
+            let path = error_envelope_field_path(key, header, file_path)?;
+            extract_json_path_value(response, &path, file_path)
+        },
+        ConditionKey::Charset => Ok(detect_charset(response)),
+        ConditionKey::ContentLanguage => Ok(joined_header_values(response, "content-language")),
+        ConditionKey::Vary => Ok(joined_header_values(response, "vary")),
+        ConditionKey::HeaderCount => Ok(response.headers.len().to_string()),
+        ConditionKey::HeaderOccurrences(name) => Ok(header_occurrences(response, name).len().to_string()),
+        ConditionKey::CacheStatus => Ok(detect_cache_status(response, header)),
+        ConditionKey::Compressed => Ok(response.content_encoding.is_some().to_string()),
+        ConditionKey::FromCache => Ok(response.from_cache.to_string()),
+        ConditionKey::RedirectCount => Ok(response.redirects.len().to_string()),
+        ConditionKey::Attempts => Ok(response.attempts.to_string()),
+        ConditionKey::FinalUrl => response.final_url.clone()
+            .ok_or_else(|| GreqError::ConditionFailed(format!(
+                "{}: final-url is unavailable, mocked and WebSocket responses are not fetched from a URL", file_path
+            ))),
+        ConditionKey::Scheme => final_url_scheme(response)
+            .ok_or_else(|| GreqError::ConditionFailed(format!(
+                "{}: scheme is unavailable, mocked and WebSocket responses are not fetched from a URL", file_path
+            ))),
+        ConditionKey::Tls(property) => {
+            let certificate = response.tls_certificate.as_ref()
+                .ok_or_else(|| GreqError::ConditionFailed(format!(
+                    "{}: tls.{} is not available, the request was not made over HTTPS", file_path, property
+                )))?;
+            if property == "version" {
+                response.tls_version.clone().ok_or_else(|| GreqError::ConditionFailed(format!(
+                    "{}: tls.version was not recorded, the connection was not made for a tls.version condition", file_path
+                )))
+            } else {
+                Ok(tls_certificate_property(certificate, property))
+            }
+        },
+        ConditionKey::Time(phase) => {
+            let timings = response.timings
+                .ok_or_else(|| GreqError::ConditionFailed(format!(
+                    "{}: time.{} is unavailable, phase timings are not captured for mocked and WebSocket responses", file_path, phase
+                )))?;
+            Ok(timing_phase_millis(&timings, phase))
         },
+        ConditionKey::ResponseBodyElements { path, element_path, .. } => {
+            let values = extract_array_element_values(response, path, element_path, file_path)?;
+            Ok(format_array_element_values(&values))
+        },
+        ConditionKey::Frame(index) => response.frames.get(*index).cloned()
+            .ok_or_else(|| GreqError::ConditionFailed(format!(
+                "{}: frame[{}] was not received ({} frames received)", file_path, index, response.frames.len()
+            ))),
+        ConditionKey::PartBody(index) => Ok(multipart_part(response, *index, file_path)?.body),
+        ConditionKey::PartHeader(index, name) => Ok(multipart_part(response, *index, file_path)?.header(name)),
     }
 }
 
 /// Extract value for exists operator - returns empty string if path doesn't exist
 /// This allows the exists operator to properly check for non-existence
-fn extract_condition_value_for_exists(key: &ConditionKey, response: &Response, file_path: &str) -> String {
-    match key {
+fn extract_condition_value_for_exists(key: &ConditionKey, response: &Response, file_path: &str, header: &Header) -> Result<String> {
+    let value = match key {
         ConditionKey::StatusCode => response.status_code.to_string(),
         ConditionKey::Latency => response.latency.as_millis().to_string(),
         ConditionKey::ResponseBody => response.body.clone(),
@@ -157,110 +665,390 @@ fn extract_condition_value_for_exists(key: &ConditionKey, response: &Response, f
         ConditionKey::Header(header_name) => {
             response.headers.get(&header_name.to_lowercase()).cloned().unwrap_or_default()
         },
+        ConditionKey::Trailer(name) => response.trailers.as_ref()
+            .and_then(|trailers| trailers.get(name).cloned())
+            .unwrap_or_default(),
         ConditionKey::ResponseBodyPath(path) => {
             // For exists operator, return empty string if path doesn't exist
-            extract_json_path_value(&response.body, path, file_path).unwrap_or_default()
+            extract_json_path_value(response, path, file_path).unwrap_or_default()
         },
-    }
+        ConditionKey::Cookie(cookie_path) => extract_cookie_value(response, cookie_path),
+        ConditionKey::LocationQuery(param) => extract_location_query_param(response, param),
+        ConditionKey::ErrorCode | ConditionKey::ErrorMessage => {
+            // The envelope must be configured, but the error object itself may be absent
+            let path = error_envelope_field_path(key, header, file_path)?;
+            extract_json_path_value(response, &path, file_path).unwrap_or_default()
+        },
+        ConditionKey::Frame(index) => response.frames.get(*index).cloned().unwrap_or_default(),
+        ConditionKey::PartBody(index) => multipart_part(response, *index, file_path)
+            .map(|part| part.body)
+            .unwrap_or_default(),
+        ConditionKey::PartHeader(index, name) => multipart_part(response, *index, file_path)
+            .map(|part| part.header(name))
+            .unwrap_or_default(),
+        ConditionKey::Charset => detect_charset(response),
+        ConditionKey::ContentLanguage => joined_header_values(response, "content-language"),
+        ConditionKey::Vary => joined_header_values(response, "vary"),
+        ConditionKey::HeaderCount => response.headers.len().to_string(),
+        ConditionKey::HeaderOccurrences(name) => header_occurrences(response, name).len().to_string(),
+        ConditionKey::CacheStatus => detect_cache_status(response, header),
+        ConditionKey::Compressed => response.content_encoding.is_some().to_string(),
+        ConditionKey::FromCache => response.from_cache.to_string(),
+        ConditionKey::RedirectCount => response.redirects.len().to_string(),
+        ConditionKey::Attempts => response.attempts.to_string(),
+        ConditionKey::FinalUrl => response.final_url.clone().unwrap_or_default(),
+        ConditionKey::Scheme => final_url_scheme(response).unwrap_or_default(),
+        ConditionKey::Tls(property) if property == "version" => response.tls_version.clone().unwrap_or_default(),
+        ConditionKey::Tls(property) => response.tls_certificate.as_ref()
+            .map(|certificate| tls_certificate_property(certificate, property))
+            .unwrap_or_default(),
+        ConditionKey::Time(phase) => response.timings
+            .map(|timings| timing_phase_millis(&timings, phase))
+            .unwrap_or_default(),
+        ConditionKey::ResponseBodyElements { path, element_path, .. } => {
+            extract_array_element_values(response, path, element_path, file_path)
+                .map(|values| format_array_element_values(&values))
+                .unwrap_or_default()
+        },
+    };
+    Ok(value)
 }
 
-/// Extract value from JSON response body using path
-fn extract_json_path_value(json_text: &str, path: &str, file_path: &str) -> Result<String> {
-    let value: Value = serde_json::from_str(json_text)
-        .map_err(|_| GreqError::ConditionFailed(format!("{}: Response body is not valid JSON", file_path)))?;
-    
-    let result = navigate_json_path(&value, path, file_path)?;
-    
-    match result {
-        Value::String(s) => Ok(s),
-        Value::Number(n) => Ok(n.to_string()),
-        Value::Bool(b) => Ok(b.to_string()),
-        Value::Null => Ok("null".to_string()),
-        _ => Ok(serde_json::to_string(&result)?),
-    }
+/// Build the JSON path of `error-code`/`error-message` inside the configured error envelope
+fn error_envelope_field_path(key: &ConditionKey, header: &Header, file_path: &str) -> Result<String> {
+    let envelope_path = header.error_envelope_path.as_ref()
+        .ok_or_else(|| GreqError::ConditionFailed(format!(
+            "{}: '{}' requires the 'error-envelope-path' header property",
+            file_path, format_condition_key(key)
+        )))?;
+    let field = if matches!(key, ConditionKey::ErrorCode) { "code" } else { "message" };
+    Ok(format!("{envelope_path}.{field}"))
 }
 
-/// Navigate JSON path similar to placeholders module
-fn navigate_json_path(value: &Value, path: &str, file_path: &str) -> Result<Value> {
-    let mut current = value;
-    let parts = parse_json_path(path, file_path)?;
-    
-    for part in parts {
-        match part {
-            PathPart::Property(key) => {
-                if let Value::Object(obj) = current {
-                    current = obj.get(&key)
-                        .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Property '{}' not found in JSON path '{}'", file_path, key, path)))?;
-                } else {
-                    return Err(GreqError::ConditionFailed(format!("{}: Cannot access property '{}' on non-object in JSON path '{}'", file_path, key, path)));
-                }
+/// A cookie parsed from a Set-Cookie response header
+#[derive(Debug, Clone)]
+struct Cookie {
+    name: String,
+    value: String,
+    /// Attribute names are lowercased; flag attributes like `Secure` have an empty value
+    attributes: HashMap<String, String>,
+}
+
+/// Parse a single Set-Cookie header value into its name, value and attributes
+fn parse_set_cookie(header_value: &str) -> Option<Cookie> {
+    let mut parts = header_value.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut attributes = HashMap::new();
+    for attribute in parts {
+        let attribute = attribute.trim();
+        if attribute.is_empty() {
+            continue;
+        }
+        match attribute.split_once('=') {
+            Some((attr_name, attr_value)) => {
+                attributes.insert(attr_name.trim().to_lowercase(), attr_value.trim().to_string());
             },
-            PathPart::Index(index) => {
-                if let Value::Array(arr) = current {
-                    current = arr.get(index)
-                        .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Array index {} out of bounds in JSON path '{}'", file_path, index, path)))?;
-                } else {
-                    return Err(GreqError::ConditionFailed(format!("{}: Cannot access index {} on non-array in JSON path '{}'", file_path, index, path)));
-                }
+            None => {
+                attributes.insert(attribute.to_lowercase(), String::new());
             },
         }
     }
-    
-    Ok(current.clone())
+
+    Some(Cookie {
+        name: name.to_string(),
+        value: value.trim().trim_matches('"').to_string(),
+        attributes,
+    })
 }
 
-#[derive(Debug, Clone)]
-enum PathPart {
-    Property(String),
-    Index(usize),
+/// Extract a cookie value (`session`) or cookie attribute (`session.secure`) from Set-Cookie headers.
+/// When the same cookie is set more than once the last one wins. Missing cookies yield an empty string,
+/// flag attributes (secure, httponly, partitioned) yield "true" or "false"
+fn extract_cookie_value(response: &Response, cookie_path: &str) -> String {
+    let (cookie_name, attribute) = match cookie_path.split_once('.') {
+        Some((name, attr)) => (name, Some(attr.to_lowercase())),
+        None => (cookie_path, None),
+    };
+
+    let cookie = response.all_headers.iter()
+        .rev()
+        .filter(|(name, _)| name == "set-cookie")
+        .filter_map(|(_, value)| parse_set_cookie(value))
+        .find(|cookie| cookie.name == cookie_name);
+
+    match (cookie, attribute) {
+        (Some(cookie), None) => cookie.value,
+        (Some(cookie), Some(attr)) if is_cookie_flag_attribute(&attr) => {
+            cookie.attributes.contains_key(&attr).to_string()
+        },
+        (Some(cookie), Some(attr)) => cookie.attributes.get(&attr).cloned().unwrap_or_default(),
+        (None, Some(attr)) if is_cookie_flag_attribute(&attr) => "false".to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+/// Scheme of the URL the final response came from, so a redirect from https to http is visible
+fn final_url_scheme(response: &Response) -> Option<String> {
+    let final_url = response.final_url.as_deref()?;
+    reqwest::Url::parse(final_url).ok().map(|url| url.scheme().to_string())
+}
+
+/// Extract a URL-decoded query parameter of the `Location` header, e.g. `state` of an OAuth redirect.
+/// The first occurrence wins; a missing header or parameter yields an empty string
+fn extract_location_query_param(response: &Response, param: &str) -> String {
+    let location = response.headers.get("location").map(String::as_str).unwrap_or_default();
+    let query = location.split('#').next().unwrap_or_default()
+        .split_once('?')
+        .map_or("", |(_, query)| query);
+
+    let decode = |text: &str| percent_encoding::percent_decode_str(&text.replace('+', " "))
+        .decode_utf8_lossy()
+        .into_owned();
+    query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .find(|(name, _)| decode(name) == param)
+        .map(|(_, value)| decode(value))
+        .unwrap_or_default()
+}
+
+/// Check whether a cookie attribute is a flag without a value
+fn is_cookie_flag_attribute(attribute: &str) -> bool {
+    matches!(attribute, "secure" | "httponly" | "partitioned")
+}
+
+/// Every value of a response header, joined with `, ` when it was sent more than once
+/// (`Vary: Accept` and `Vary: Accept-Language`); empty when the header is absent
+fn joined_header_values(response: &Response, name: &str) -> String {
+    header_occurrences(response, name).join(", ")
+}
+
+/// One part of a multipart response body
+#[derive(Debug, Clone, PartialEq)]
+struct MultipartPart {
+    /// Part headers with lowercased names, in order
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MultipartPart {
+    /// A part header, repeated ones joined with `, `; empty when absent
+    fn header(&self, name: &str) -> String {
+        self.headers.iter()
+            .filter(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The n-th part of a `multipart/*` response, split at the boundary of its Content-Type
+fn multipart_part(response: &Response, index: usize, file_path: &str) -> Result<MultipartPart> {
+    let content_type = response.headers.get("content-type").map(String::as_str).unwrap_or_default();
+    let boundary = multipart_boundary(content_type).ok_or_else(|| GreqError::ConditionFailed(format!(
+        "{}: part[{}] needs a multipart response with a boundary, content-type is '{}'", file_path, index, content_type
+    )))?;
+    let mut parts = parse_multipart(&response.body, boundary);
+    if index >= parts.len() {
+        return Err(GreqError::ConditionFailed(format!(
+            "{}: part[{}] does not exist, the multipart response has {} parts", file_path, index, parts.len()
+        )));
+    }
+    Ok(parts.swap_remove(index))
+}
+
+/// The `boundary` parameter of a `multipart/*` content type, without quotes
+fn multipart_boundary(content_type: &str) -> Option<&str> {
+    let mut params = content_type.split(';');
+    if !params.next()?.trim().to_lowercase().starts_with("multipart/") {
+        return None;
+    }
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+        .filter(|boundary| !boundary.is_empty())
 }
 
-fn parse_json_path(path: &str, file_path: &str) -> Result<Vec<PathPart>> {
+/// Split a multipart body into its parts (RFC 2046): the preamble before the first delimiter and
+/// the epilogue after the closing one are ignored, and the line break before a delimiter belongs to it
+fn parse_multipart(body: &str, boundary: &str) -> Vec<MultipartPart> {
+    let delimiter = format!("--{boundary}");
     let mut parts = Vec::new();
-    let mut current = String::new();
-    let mut chars = path.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        match ch {
-            '.' => {
-                if !current.is_empty() {
-                    parts.push(PathPart::Property(current.clone()));
-                    current.clear();
-                }
-            },
-            '[' => {
-                if !current.is_empty() {
-                    parts.push(PathPart::Property(current.clone()));
-                    current.clear();
-                }
-                
-                let mut index_str = String::new();
-                for ch in chars.by_ref() {
-                    if ch == ']' {
-                        break;
-                    }
-                    index_str.push(ch);
-                }
-                
-                let index: usize = index_str.parse()
-                    .map_err(|_| GreqError::ConditionFailed(format!("{}: Invalid array index: {index_str}", file_path)))?;
-                parts.push(PathPart::Index(index));
-            },
-            _ => {
-                current.push(ch);
-            }
+    for section in body.split(delimiter.as_str()).skip(1) {
+        if section.starts_with("--") {
+            break;
         }
+        // Skip transport padding and the line break after the delimiter
+        let section = section.split_once('\n').map(|(_, rest)| rest).unwrap_or_default();
+        let section = section.strip_suffix("\r\n").or_else(|| section.strip_suffix('\n')).unwrap_or(section);
+
+        let (head, body) = if let Some(body) = section.strip_prefix("\r\n").or_else(|| section.strip_prefix('\n')) {
+            ("", body)
+        } else {
+            let crlf = section.find("\r\n\r\n").map(|at| (at, 4));
+            let lf = section.find("\n\n").map(|at| (at, 2));
+            match crlf.into_iter().chain(lf).min() {
+                Some((at, len)) => (&section[..at], &section[at + len..]),
+                None => (section, ""),
+            }
+        };
+        let headers = head.lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+        parts.push(MultipartPart { headers, body: body.to_string() });
+    }
+    parts
+}
+
+/// Every value of a header in received order, one per occurrence
+fn header_occurrences<'a>(response: &'a Response, name: &str) -> Vec<&'a str> {
+    let values: Vec<&str> = response.all_headers
+        .iter()
+        .filter(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+        .collect();
+    if values.is_empty() {
+        response.headers.get(name).map(String::as_str).into_iter().collect()
+    } else {
+        values
+    }
+}
+
+/// Detect the response charset from the `charset=` parameter of Content-Type.
+/// Without one, a body that decoded cleanly is reported as utf-8 and one with replacement characters as unknown
+fn detect_charset(response: &Response) -> String {
+    let declared = response.headers.get("content-type").and_then(|content_type| {
+        content_type
+            .split(';')
+            .skip(1)
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value.trim().trim_matches('"').to_lowercase())
+    });
+    
+    match declared {
+        Some(charset) => charset,
+        None if response.body.is_empty() => String::new(),
+        None if response.body.contains('\u{FFFD}') => "unknown".to_string(),
+        None => "utf-8".to_string(),
+    }
+}
+
+/// A `tls.<property>` value of the server certificate; days until expiry are negative for expired certificates
+fn tls_certificate_property(certificate: &PeerCertificate, property: &str) -> String {
+    match property {
+        "days-until-expiry" => (certificate.not_after - Utc::now()).num_days().to_string(),
+        "issuer" => certificate.issuer.clone(),
+        "subject" => certificate.subject.clone(),
+        _ => String::new(),
     }
+}
+
+/// A `time.<phase>` value in whole milliseconds, like `latency`
+fn timing_phase_millis(timings: &ResponseTimings, phase: &str) -> String {
+    let duration = match phase {
+        "connect" => timings.connect,
+        "ttfb" => timings.ttfb,
+        "download" => timings.download,
+        _ => return String::new(),
+    };
+    duration.as_millis().to_string()
+}
+
+/// Cache headers recognized by `cache-status`, in order of preference
+const CACHE_STATUS_HEADERS: &[&str] = &["cache-status", "cf-cache-status", "x-cache", "x-cache-status", "x-drupal-cache", "age"];
+
+/// Normalize the cache headers of a response to `hit` or `miss`; empty when none is present.
+/// `cache-header` selects the header to read instead of the recognized ones
+fn detect_cache_status(response: &Response, header: &Header) -> String {
+    let candidates: Vec<&str> = match &header.cache_header {
+        Some(name) => vec![name.as_str()],
+        None => CACHE_STATUS_HEADERS.to_vec(),
+    };
+    
+    candidates
+        .into_iter()
+        .find_map(|name| response.headers.get(name).map(|value| normalize_cache_status(name, value)))
+        .unwrap_or_default()
+}
+
+fn normalize_cache_status(header_name: &str, value: &str) -> String {
+    // Multi-layer values list the cache closest to the client last (`MISS, HIT`, RFC 9211 Cache-Status)
+    let value = value.rsplit(',').next().unwrap_or_default().trim().to_lowercase();
     
-    if !current.is_empty() {
-        parts.push(PathPart::Property(current));
+    let is_hit = match header_name {
+        "age" => value.parse::<u64>().is_ok_and(|age| age > 0),
+        // RFC 9211: `ExampleCache; hit` or `ExampleCache; fwd=uri-miss`
+        "cache-status" => value.split(';').skip(1).any(|param| param.trim() == "hit"),
+        // Served from cache, possibly after revalidation or while refreshing
+        _ => ["hit", "stale", "revalidated", "updating"].iter().any(|word| value.contains(word)),
+    };
+    
+    if is_hit { "hit" } else { "miss" }.to_string()
+}
+
+/// Extract value from JSON response body using path, optionally piped through functions (`users | length`)
+fn extract_json_path_value(response: &Response, path: &str, file_path: &str) -> Result<String> {
+    extract_json_path_json(response, path, file_path).map(|value| json_value_to_string(&value))
+}
+
+/// The JSON value at a response body path, after the piped functions
+fn extract_json_path_json(response: &Response, path: &str, file_path: &str) -> Result<Value> {
+    let value = response.json()
+        .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Response body is not valid JSON", file_path)))?;
+    
+    let mut segments = path.split('|').map(str::trim);
+    let mut result = navigate_json_path(value, segments.next().unwrap_or_default())
+        .map_err(|e| GreqError::ConditionFailed(format!("{}: {}", file_path, e)))?
+        .clone();
+    for function in segments {
+        result = apply_json_function(result, function, file_path)?;
     }
     
-    Ok(parts)
+    Ok(result)
+}
+
+/// Apply a jq-like aggregation function to a JSON value
+fn apply_json_function(value: Value, function: &str, file_path: &str) -> Result<Value> {
+    let unsupported = |value: &Value| GreqError::ConditionFailed(format!(
+        "{}: Cannot apply '{}' to {}", file_path, function, json_type_name(value)
+    ));
+    
+    match (function, value) {
+        ("length", Value::Array(arr)) => Ok(Value::from(arr.len())),
+        ("length", Value::Object(obj)) => Ok(Value::from(obj.len())),
+        ("length", Value::String(s)) => Ok(Value::from(s.chars().count())),
+        ("length", Value::Null) => Ok(Value::from(0)),
+        ("keys", Value::Object(obj)) => {
+            let mut keys: Vec<String> = obj.keys().cloned().collect();
+            keys.sort();
+            Ok(Value::from(keys))
+        },
+        ("keys", Value::Array(arr)) => Ok(Value::from((0..arr.len()).collect::<Vec<_>>())),
+        ("values", Value::Object(obj)) => Ok(Value::Array(obj.into_iter().map(|(_, v)| v).collect())),
+        ("values", Value::Array(arr)) => Ok(Value::Array(arr)),
+        ("first", Value::Array(arr)) => Ok(arr.into_iter().next().unwrap_or(Value::Null)),
+        ("last", Value::Array(arr)) => Ok(arr.into_iter().last().unwrap_or(Value::Null)),
+        ("length" | "keys" | "values" | "first" | "last", other) => Err(unsupported(&other)),
+        (_, _) => Err(GreqError::ConditionFailed(format!("{}: Unknown JSON function '{}'", file_path, function))),
+    }
 }
 
 // Comparison functions
 fn compare_equals(actual: &str, expected: &str, case_sensitive: bool) -> bool {
+    // Integers are compared by value so large IDs never go through a lossy conversion. Only
+    // canonical integers qualify: `007` and `+1` stay strings, e.g. zip codes or padded ids
+    if let (Some(actual_int), Some(expected_int)) = (canonical_integer(actual), canonical_integer(expected)) {
+        return actual_int == expected_int;
+    }
+    
     if case_sensitive {
         actual == expected
     } else {
@@ -276,6 +1064,26 @@ fn compare_contains(actual: &str, expected: &str, case_sensitive: bool) -> bool
     }
 }
 
+/// The first entry of a comma-separated list that occurs in the value, for `contains-any`
+fn first_listed_match<'a>(actual: &str, list: &'a str, case_sensitive: bool, file_path: &str) -> Result<Option<&'a str>> {
+    let entries: Vec<&str> = list.split(',').map(str::trim).filter(|entry| !entry.is_empty()).collect();
+    if entries.is_empty() {
+        return Err(GreqError::ConditionFailed(format!(
+            "{}: contains-any expects a comma-separated list of values, e.g. password, secret", file_path
+        )));
+    }
+    Ok(entries.into_iter().find(|entry| compare_contains(actual, entry, case_sensitive)))
+}
+
+/// An integer written the way JSON writes numbers: an optional `-`, no `+` and no leading zeros
+fn canonical_integer(text: &str) -> Option<i128> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let canonical = !digits.is_empty()
+        && digits.bytes().all(|byte| byte.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'));
+    if canonical { text.parse().ok() } else { None }
+}
+
 fn compare_regex(actual: &str, pattern: &str) -> Result<bool> {
     let regex = Regex::new(pattern)?;
     Ok(regex.is_match(actual))
@@ -283,76 +1091,437 @@ fn compare_regex(actual: &str, pattern: &str) -> Result<bool> {
 
 fn compare_numeric<F>(actual: &str, expected: &str, file_path: &str, op: F) -> Result<bool>
 where
-    F: Fn(f64, f64) -> bool,
+    F: Fn(Ordering) -> bool,
 {
+    // Compare integers exactly; f64 cannot represent integers beyond 2^53
+    if let (Ok(actual_int), Ok(expected_int)) = (actual.parse::<i128>(), expected.parse::<i128>()) {
+        return Ok(op(actual_int.cmp(&expected_int)));
+    }
+    
     let actual_num: f64 = actual.parse()
         .map_err(|_| GreqError::ConditionFailed(format!("{}: Cannot parse '{actual}' as number", file_path)))?;
     let expected_num: f64 = expected.parse()
         .map_err(|_| GreqError::ConditionFailed(format!("{}: Cannot parse '{expected}' as number", file_path)))?;
     
-    Ok(op(actual_num, expected_num))
+    Ok(actual_num.partial_cmp(&expected_num).is_some_and(op))
 }
 
-fn compare_starts_with(actual: &str, expected: &str, case_sensitive: bool) -> bool {
-    if case_sensitive {
-        actual.starts_with(expected)
-    } else {
-        actual.to_lowercase().starts_with(&expected.to_lowercase())
-    }
+/// Check that a number lies in `[VALUE - TOLERANCE, VALUE + TOLERANCE]`
+fn compare_within(actual: &str, expected: &str, file_path: &str) -> Result<bool> {
+    let (low, high) = parse_tolerance_range(expected)
+        .ok_or_else(|| GreqError::ConditionFailed(format!(
+            "{}: Invalid tolerance '{}', expected VALUE±TOLERANCE or VALUE+/-TOLERANCE (e.g. 500±50)", file_path, expected
+        )))?;
+    let actual_num: f64 = actual.trim().parse()
+        .map_err(|_| GreqError::ConditionFailed(format!("{}: Cannot parse '{actual}' as number", file_path)))?;
+    
+    Ok(actual_num >= low && actual_num <= high)
 }
 
-fn compare_ends_with(actual: &str, expected: &str, case_sensitive: bool) -> bool {
-    if case_sensitive {
-        actual.ends_with(expected)
+/// Tolerance of `approx-equals` when the expected value has no `±`, enough to absorb
+/// floating point rounding such as `0.1 + 0.2`
+const DEFAULT_APPROX_TOLERANCE: f64 = 1e-9;
+
+/// Check that two numbers differ by at most the tolerance: `19.99±0.01`, or a plain `19.99`
+/// compared with `DEFAULT_APPROX_TOLERANCE`
+fn compare_approx_equals(actual: &str, expected: &str, file_path: &str) -> Result<bool> {
+    let (low, high) = if expected.contains('±') || expected.contains("+/-") {
+        parse_tolerance_range(expected)
     } else {
-        actual.to_lowercase().ends_with(&expected.to_lowercase())
+        expected.trim().parse::<f64>().ok()
+            .filter(|value| value.is_finite())
+            .map(|value| (value - DEFAULT_APPROX_TOLERANCE, value + DEFAULT_APPROX_TOLERANCE))
     }
-}
+    .ok_or_else(|| GreqError::ConditionFailed(format!(
+        "{}: approx-equals expects a number with an optional tolerance (e.g. 19.99±0.01), got '{}'", file_path, expected
+    )))?;
+    let actual_num: f64 = actual.trim().parse()
+        .map_err(|_| GreqError::ConditionFailed(format!("{}: approx-equals cannot compare '{actual}', it is not a number", file_path)))?;
 
-fn compare_exists(actual: &str, expected: &str, file_path: &str) -> Result<bool> {
-    let expected_exists: bool = expected.parse()
-        .map_err(|_| GreqError::ConditionFailed(format!("{}: Invalid boolean value for exists: {expected}", file_path)))?;
-    
-    let actual_exists = !actual.is_empty();
-    Ok(actual_exists == expected_exists)
+    Ok(actual_num >= low && actual_num <= high)
 }
 
-// Formatting functions for error messages
-fn format_failed_condition_with_actual(condition: &Condition, actual_value: &str) -> String {
-    let mut parts = Vec::new();
-    
-    if condition.is_not {
-        parts.push("NOT".to_string());
-    }
+/// Parse `500±50` or `500+/-50` into the inclusive range `(450, 550)`
+fn parse_tolerance_range(expression: &str) -> Option<(f64, f64)> {
+    let (value, tolerance) = expression.split_once('±')
+        .or_else(|| expression.split_once("+/-"))?;
+    let value: f64 = value.trim().parse().ok()?;
+    let tolerance: f64 = tolerance.trim().parse().ok()?;
     
-    parts.push(format_condition_key(&condition.key));
-    parts.push(format_operator(&condition.operator));
-    
-    if condition.case_sensitive {
-        parts.push("case-sensitive".to_string());
+    if tolerance < 0.0 || !value.is_finite() || !tolerance.is_finite() {
+        return None;
     }
     
-    parts.push(format!("'{}'", condition.value));
-    parts.push(format!("(actual: '{}')", actual_value));
-    
-    parts.join(" ")
+    Some((value - tolerance, value + tolerance))
 }
 
-fn format_condition_key(key: &ConditionKey) -> String {
+/// Compare two JSON documents as values; an actual value that is not JSON never matches
+fn compare_json_equals(actual: &str, expected: &str, file_path: &str) -> Result<bool> {
+    let expected: Value = serde_json::from_str(expected)
+        .map_err(|e| GreqError::ConditionFailed(format!("{}: Expected value of json-equals is not valid JSON: {}", file_path, e)))?;
+    let Ok(actual) = serde_json::from_str::<Value>(actual) else {
+        return Ok(false);
+    };
+    
+    let mut differences = Vec::new();
+    collect_json_differences(&actual, &expected, "$", false, &mut differences);
+    Ok(differences.is_empty())
+}
+
+/// Read the expected content of a fixture file, resolved relative to the `.greq` file
+fn read_fixture(fixture: &str, file_path: &str) -> Result<String> {
+    let path = resolve_relative_path(file_path, fixture);
+    fs::read_to_string(&path).map_err(|e| GreqError::ConditionFailed(format!(
+        "{}: Cannot read fixture file '{}' ({}): {}", file_path, fixture, path, e
+    )))
+}
+
+/// Read a fixture file as bytes, resolved relative to the `.greq` file
+fn read_binary_fixture(fixture: &str, file_path: &str) -> Result<Vec<u8>> {
+    let path = resolve_relative_path(file_path, fixture);
+    fs::read(&path).map_err(|e| GreqError::ConditionFailed(format!(
+        "{}: Cannot read fixture file '{}' ({}): {}", file_path, fixture, path, e
+    )))
+}
+
+/// Compare a value byte for byte with a fixture file. `response-body` uses the raw body, so
+/// binary payloads are not corrupted by text decoding; the actual value reports the size and,
+/// on a mismatch, the first differing byte offset
+fn evaluate_binary_file(condition: &Condition, response: &Response, file_path: &str, header: &Header) -> Result<(bool, String)> {
+    let actual = match &condition.key {
+        ConditionKey::ResponseBody => response.body_bytes().to_vec(),
+        key => extract_condition_value(key, response, file_path, header)?.into_bytes(),
+    };
+    let expected = read_binary_fixture(&condition.value, file_path)?;
+
+    match describe_first_byte_difference(&actual, &expected) {
+        None => Ok((true, format!("{} bytes", actual.len()))),
+        Some(difference) => Ok((false, format!("{} bytes; {}", actual.len(), difference))),
+    }
+}
+
+/// JSON types accepted by `type-equals`
+const JSON_TYPES: &[&str] = &["string", "number", "boolean", "array", "object", "null"];
+
+/// Check the JSON type of the value at a response-body path; the actual value is the detected type
+fn evaluate_type_equals(condition: &Condition, response: &Response, file_path: &str) -> Result<(bool, String)> {
+    let expected = condition.value.trim().to_lowercase();
+    if !JSON_TYPES.contains(&expected.as_str()) {
+        return Err(GreqError::ConditionFailed(format!(
+            "{}: type-equals expects one of {}, got '{}'", file_path, JSON_TYPES.join(", "), condition.value
+        )));
+    }
+    let value = match &condition.key {
+        ConditionKey::ResponseBody => response.json()
+            .cloned()
+            .ok_or_else(|| GreqError::ConditionFailed(format!("{}: Response body is not valid JSON", file_path)))?,
+        ConditionKey::ResponseBodyPath(path) => extract_json_path_json(response, path, file_path)?,
+        key => return Err(GreqError::ConditionFailed(format!(
+            "{}: type-equals only works on response-body and response-body.<path> keys, not '{}'", file_path, format_condition_key(key)
+        ))),
+    };
+
+    let actual = json_type_keyword(&value);
+    Ok((actual == expected, actual.to_string()))
+}
+
+/// The `type-equals` name of a JSON value's type
+fn json_type_keyword(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Point at the first byte offset where two byte strings differ; `None` when they are equal
+fn describe_first_byte_difference(actual: &[u8], expected: &[u8]) -> Option<String> {
+    match actual.iter().zip(expected).position(|(a, e)| a != e) {
+        Some(offset) => Some(format!(
+            "first difference at byte {}: 0x{:02x} instead of 0x{:02x}", offset, actual[offset], expected[offset]
+        )),
+        None if actual.len() < expected.len() => Some(format!("ends at byte {}, the file has {} bytes", actual.len(), expected.len())),
+        None if actual.len() > expected.len() => Some(format!("{} extra bytes after byte {}", actual.len() - expected.len(), expected.len())),
+        None => None,
+    }
+}
+
+/// Point at the first line where a value differs from its fixture
+fn describe_first_line_difference(actual: &str, expected: &str) -> String {
+    let mut actual_lines = actual.trim_end().lines();
+    let mut expected_lines = expected.trim_end().lines();
+    let mut line = 1;
+    loop {
+        match (actual_lines.next(), expected_lines.next()) {
+            (Some(a), Some(e)) if a == e => line += 1,
+            (a, e) => return format!(
+                "first difference at line {}: expected '{}', got '{}'",
+                line,
+                e.unwrap_or("<end of fixture>"),
+                a.unwrap_or("<end of response>")
+            ),
+        }
+    }
+}
+
+/// Rewrite the fixture files of failing `equals-file` and `json-equals-file` conditions with
+/// the actual values for `--update-fixtures`. Returns the paths that were written
+pub fn update_fixture_files(conditions: &[Condition], response: &Response, file_path: &str, header: &Header) -> Result<Vec<String>> {
+    let mut updated = Vec::new();
+    
+    for condition in conditions {
+        if !matches!(condition.operator, Operator::EqualsFile | Operator::JsonEqualsFile) || condition.is_not {
+            continue;
+        }
+        let actual_value = extract_condition_value(&condition.key, response, file_path, header)?;
+        let matches = read_fixture(&condition.value, file_path)
+            .and_then(|_| compare_values(&condition.operator, &actual_value, &condition.value, condition.case_sensitive, file_path))
+            .unwrap_or(false);
+        if matches {
+            continue;
+        }
+        
+        // JSON fixtures are stored pretty-printed so later diffs stay readable
+        let content = match serde_json::from_str::<Value>(&actual_value) {
+            Ok(value) if condition.operator == Operator::JsonEqualsFile => serde_json::to_string_pretty(&value)? + "\n",
+            _ => actual_value,
+        };
+        let path = resolve_relative_path(file_path, &condition.value);
+        fs::write(&path, content)?;
+        updated.push(path);
+    }
+    
+    Ok(updated)
+}
+
+/// Differences shown in a json-equals failure, e.g. `$.user.name: 'Bob' instead of 'Ann'`
+const MAX_JSON_DIFFERENCES: usize = 5;
+
+fn describe_json_differences(actual: &str, expected: &str) -> String {
+    let (Ok(actual), Ok(expected)) = (serde_json::from_str::<Value>(actual), serde_json::from_str::<Value>(expected)) else {
+        return "response is not valid JSON".to_string();
+    };
+    
+    summarize_json_differences(&actual, &expected).join("; ")
+}
+
+/// The first differences between two JSON values, followed by a count of the rest
+pub fn summarize_json_differences(actual: &Value, expected: &Value) -> Vec<String> {
+    let mut differences = Vec::new();
+    collect_json_differences(actual, expected, "$", false, &mut differences);
+    let more = differences.len().saturating_sub(MAX_JSON_DIFFERENCES);
+    differences.truncate(MAX_JSON_DIFFERENCES);
+    if more > 0 {
+        differences.push(format!("{more} more"));
+    }
+    differences
+}
+
+/// The first path where the actual JSON lacks or contradicts the expected subset, for `json-contains`
+fn first_json_contains_difference(actual: &str, expected: &str, file_path: &str) -> Result<Option<String>> {
+    let expected: Value = serde_json::from_str(expected)
+        .map_err(|e| GreqError::ConditionFailed(format!("{}: Expected value of json-contains is not valid JSON: {}", file_path, e)))?;
+    let Ok(actual) = serde_json::from_str::<Value>(actual) else {
+        return Ok(Some("response is not valid JSON".to_string()));
+    };
+    
+    let mut differences = Vec::new();
+    collect_json_differences(&actual, &expected, "$", true, &mut differences);
+    Ok(differences.into_iter().next())
+}
+
+/// Walk both values, recording where they differ. Object key order is ignored and
+/// numbers are compared by value, so `1` equals `1.0`. With `subset`, objects may have keys
+/// the expected value does not mention, at any depth
+fn collect_json_differences(actual: &Value, expected: &Value, path: &str, subset: bool, differences: &mut Vec<String>) {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            for (key, expected_value) in expected {
+                match actual.get(key) {
+                    Some(actual_value) => collect_json_differences(actual_value, expected_value, &format!("{path}.{key}"), subset, differences),
+                    None => differences.push(format!("{path}.{key}: missing")),
+                }
+            }
+            if !subset {
+                for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                    differences.push(format!("{path}.{key}: unexpected"));
+                }
+            }
+        },
+        (Value::Array(actual), Value::Array(expected)) if actual.len() == expected.len() => {
+            for (index, (actual_value, expected_value)) in actual.iter().zip(expected).enumerate() {
+                collect_json_differences(actual_value, expected_value, &format!("{path}[{index}]"), subset, differences);
+            }
+        },
+        (Value::Array(actual), Value::Array(expected)) => {
+            differences.push(format!("{path}: {} elements instead of {}", actual.len(), expected.len()));
+        },
+        (Value::Number(actual), Value::Number(expected))
+            if actual == expected || ((actual.is_f64() || expected.is_f64()) && actual.as_f64() == expected.as_f64()) => {},
+        _ if actual == expected => {},
+        _ => differences.push(format!("{path}: {actual} instead of {expected}")),
+    }
+}
+
+fn compare_starts_with(actual: &str, expected: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        actual.starts_with(expected)
+    } else {
+        actual.to_lowercase().starts_with(&expected.to_lowercase())
+    }
+}
+
+fn compare_ends_with(actual: &str, expected: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        actual.ends_with(expected)
+    } else {
+        actual.to_lowercase().ends_with(&expected.to_lowercase())
+    }
+}
+
+fn compare_exists(actual: &str, expected: &str, file_path: &str) -> Result<bool> {
+    let expected_exists: bool = expected.parse()
+        .map_err(|_| GreqError::ConditionFailed(format!("{}: Invalid boolean value for exists: {expected}", file_path)))?;
+    
+    let actual_exists = !actual.is_empty();
+    Ok(actual_exists == expected_exists)
+}
+
+/// Describe why an array fails `sorted-ascending`, `sorted-descending` or `equals-sequence`,
+/// pinpointing the first offending element. `None` when the condition holds or for other operators
+fn sequence_mismatch(operator: &Operator, actual: &str, expected: &str, case_sensitive: bool, file_path: &str) -> Result<Option<String>> {
+    let elements = match operator {
+        Operator::SortedAscending | Operator::SortedDescending | Operator::EqualsSequence => match serde_json::from_str::<Value>(actual) {
+            Ok(Value::Array(elements)) => elements,
+            _ => return Err(GreqError::ConditionFailed(format!(
+                "{}: {} needs a JSON array, got '{}'", file_path, format_operator(operator), actual
+            ))),
+        },
+        _ => return Ok(None),
+    };
+    
+    if *operator == Operator::EqualsSequence {
+        let expected: Vec<&str> = if expected.trim().is_empty() {
+            Vec::new()
+        } else {
+            expected.split(',').map(str::trim).collect()
+        };
+        let actual: Vec<String> = elements.iter().map(json_value_to_string).collect();
+        
+        return Ok(match actual.iter().zip(&expected).position(|(a, e)| !compare_equals(a, e, case_sensitive)) {
+            Some(index) => Some(format!("first difference at index {}: expected '{}', got '{}'", index, expected[index], actual[index])),
+            None if actual.len() > expected.len() => Some(format!("unexpected element '{}' at index {}", actual[expected.len()], expected.len())),
+            None if actual.len() < expected.len() => Some(format!("missing '{}' at index {}", expected[actual.len()], actual.len())),
+            None => None,
+        });
+    }
+    
+    let expect_sorted: bool = expected.trim().parse().map_err(|_| GreqError::ConditionFailed(format!(
+        "{}: Invalid boolean value for {}: {expected}", file_path, format_operator(operator)
+    )))?;
+    let out_of_order = if *operator == Operator::SortedAscending { Ordering::Greater } else { Ordering::Less };
+    let violation = elements
+        .windows(2)
+        .position(|pair| compare_elements(&pair[0], &pair[1], case_sensitive) == out_of_order);
+    
+    Ok(match (violation, expect_sorted) {
+        (Some(index), true) => Some(format!(
+            "first out-of-order element at index {}: {} after {}", index + 1, elements[index + 1], elements[index]
+        )),
+        (None, false) => Some("the array is sorted".to_string()),
+        _ => None,
+    })
+}
+
+/// Order two array elements: numerically when both are numbers, otherwise as text
+fn compare_elements(a: &Value, b: &Value, case_sensitive: bool) -> Ordering {
+    if let (Some(a), Some(b)) = (a.as_f64(), b.as_f64()) {
+        return a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    }
+    let (a, b) = (json_value_to_string(a), json_value_to_string(b));
+    if case_sensitive {
+        a.cmp(&b)
+    } else {
+        a.to_lowercase().cmp(&b.to_lowercase())
+    }
+}
+
+// Formatting functions for error messages
+fn format_failed_condition_with_actual(condition: &Condition, actual_value: &str) -> String {
+    format!("{} (actual: '{}')", format_condition(condition), actual_value)
+}
+
+/// Describe a condition as `[name] NOT key operator 'value'`
+fn format_condition(condition: &Condition) -> String {
+    let mut parts = Vec::new();
+    
+    if let Some(name) = &condition.name {
+        parts.push(format!("[{name}]"));
+    }
+    
+    if condition.is_not {
+        parts.push("NOT".to_string());
+    }
+    
+    parts.push(format_condition_key(&condition.key));
+    parts.push(format_operator(&condition.operator));
+    
+    if condition.case_sensitive {
+        parts.push("case-sensitive".to_string());
+    }
+    
+    parts.push(format!("'{}'", condition.value));
+    
+    parts.join(" ")
+}
+
+pub fn format_condition_key(key: &ConditionKey) -> String {
     match key {
         ConditionKey::StatusCode => "status-code".to_string(),
         ConditionKey::Latency => "latency".to_string(),
         ConditionKey::ResponseBody => "response-body".to_string(),
         ConditionKey::Headers => "headers".to_string(),
         ConditionKey::Header(name) => format!("headers.{name}"),
+        ConditionKey::Trailer(name) => format!("trailers.{name}"),
+        ConditionKey::ResponseBodyPath(path) if path.starts_with('|') => format!("response-body {path}"),
         ConditionKey::ResponseBodyPath(path) => format!("response-body.{path}"),
+        ConditionKey::Cookie(name) => format!("cookie.{name}"),
+        ConditionKey::LocationQuery(param) => format!("location.query.{param}"),
+        ConditionKey::ErrorCode => "error-code".to_string(),
+        ConditionKey::ErrorMessage => "error-message".to_string(),
+        ConditionKey::Frame(index) => format!("frame[{index}]"),
+        ConditionKey::PartBody(index) => format!("part[{index}].body"),
+        ConditionKey::PartHeader(index, name) => format!("part[{index}].headers.{name}"),
+        ConditionKey::Charset => "charset".to_string(),
+        ConditionKey::ContentLanguage => "content-language".to_string(),
+        ConditionKey::Vary => "vary".to_string(),
+        ConditionKey::HeaderCount => "header-count".to_string(),
+        ConditionKey::HeaderOccurrences(name) => format!("header-count.{name}"),
+        ConditionKey::CacheStatus => "cache-status".to_string(),
+        ConditionKey::Compressed => "compressed".to_string(),
+        ConditionKey::FromCache => "from-cache".to_string(),
+        ConditionKey::RedirectCount => "redirect-count".to_string(),
+        ConditionKey::Attempts => "attempts".to_string(),
+        ConditionKey::FinalUrl => "final-url".to_string(),
+        ConditionKey::Scheme => "scheme".to_string(),
+        ConditionKey::Tls(property) => format!("tls.{property}"),
+        ConditionKey::Time(phase) => format!("time.{phase}"),
+        ConditionKey::ResponseBodyElements { path, quantifier, element_path } => {
+            let quantifier = if *quantifier == Quantifier::Any { "any" } else { "all" };
+            let array = if path.is_empty() { "response-body".to_string() } else { format!("response-body.{path}") };
+            format!("{array} {quantifier} .{element_path}")
+        },
     }
 }
 
-fn format_operator(operator: &Operator) -> String {
+pub fn format_operator(operator: &Operator) -> String {
     match operator {
         Operator::Equals => "equals".to_string(),
         Operator::Contains => "contains".to_string(),
+        Operator::ContainsAny => "contains-any".to_string(),
         Operator::MatchesRegex => "matches-regex".to_string(),
         Operator::LessThan => "less-than".to_string(),
         Operator::LessThanOrEqual => "less-than-or-equal".to_string(),
@@ -360,6 +1529,17 @@ fn format_operator(operator: &Operator) -> String {
         Operator::GreaterThanOrEqual => "greater-than-or-equal".to_string(),
         Operator::StartsWith => "starts-with".to_string(),
         Operator::EndsWith => "ends-with".to_string(),
+        Operator::Within => "within".to_string(),
+        Operator::ApproxEquals => "approx-equals".to_string(),
+        Operator::JsonEquals => "json-equals".to_string(),
+        Operator::JsonContains => "json-contains".to_string(),
+        Operator::EqualsFile => "equals-file".to_string(),
+        Operator::JsonEqualsFile => "json-equals-file".to_string(),
+        Operator::EqualsBinaryFile => "equals-binary-file".to_string(),
+        Operator::TypeEquals => "type-equals".to_string(),
+        Operator::SortedAscending => "sorted-ascending".to_string(),
+        Operator::SortedDescending => "sorted-descending".to_string(),
+        Operator::EqualsSequence => "equals-sequence".to_string(),
         Operator::Exists => "exists".to_string(),
     }
 }
@@ -367,13 +1547,12 @@ fn format_operator(operator: &Operator) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use std::time::Duration;
     
     // Helper function for tests to get just the boolean result
     fn evaluate_single_condition_test(condition: &Condition, response: &Response) -> Result<bool> {
-        match evaluate_single_condition_with_details(condition, response, "test-file.greq")? {
-            ConditionResult::Passed => Ok(true),
+        match evaluate_single_condition_with_details(condition, response, "test-file.greq", &Header::default())? {
+            ConditionResult::Passed { .. } => Ok(true),
             ConditionResult::Failed { .. } => Ok(false),
         }
     }
@@ -387,139 +1566,567 @@ mod tests {
             headers,
             body: r#"{"id": 123, "name": "test", "items": [{"id": 1}, {"id": 2}]}"#.to_string(),
             latency: Duration::from_millis(150),
+            ..Response::default()
         }
     }
     
     #[test]
-    fn test_evaluate_status_code_condition() {
-        let response = create_test_response();
-        let condition = Condition {
+    fn test_json_equals() {
+        let condition = |value: &str| Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
-            key: ConditionKey::StatusCode,
-            operator: Operator::Equals,
+            key: ConditionKey::ResponseBody,
+            operator: Operator::JsonEquals,
             case_sensitive: false,
-            value: "200".to_string(),
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result);
+        let response = Response {
+            body: "{\n  \"id\": 7,\n  \"tags\": [\"a\", \"b\"],\n  \"user\": {\"name\": \"Ann\", \"age\": 30}\n}".to_string(),
+            ..Response::default()
+        };
+
+        assert!(evaluate_single_condition_test(&condition(r#"{"user":{"age":30.0,"name":"Ann"},"tags":["a","b"],"id":7}"#), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition(r#"{"user":{"age":30,"name":"Ann"},"tags":["b","a"],"id":7}"#), &response).unwrap());
+
+        let failed = evaluate_single_condition_with_details(&condition(r#"{"id":8,"tags":["a"],"user":{"name":"Ann","age":30,"email":"a@b.c"}}"#), &response, "test.greq", &Header::default()).unwrap();
+        let ConditionResult::Failed { actual_value, .. } = failed else { panic!("json-equals should fail") };
+        assert!(actual_value.ends_with("differences: $.id: 7 instead of 8; $.tags: 2 elements instead of 1; $.user.email: missing"), "{actual_value}");
     }
-    
+
     #[test]
-    fn test_evaluate_response_body_contains() {
-        let response = create_test_response();
-        let condition = Condition {
+    fn test_json_contains() {
+        let condition = |value: &str| Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
             key: ConditionKey::ResponseBody,
-            operator: Operator::Contains,
+            operator: Operator::JsonContains,
             case_sensitive: false,
-            value: "test".to_string(),
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result);
+        let response = Response {
+            body: r#"{"id": 7, "user": {"name": "Ann", "role": "admin", "since": 2020}, "tags": [{"id": 1, "label": "a"}]}"#.to_string(),
+            ..Response::default()
+        };
+
+        assert!(evaluate_single_condition_test(&condition(r#"{"user": {"role": "admin"}, "tags": [{"id": 1.0}]}"#), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("{}"), &response).unwrap());
+
+        let failed = evaluate_single_condition_with_details(&condition(r#"{"id": 7, "user": {"role": "owner", "since": 2020}}"#), &response, "test.greq", &Header::default()).unwrap();
+        let ConditionResult::Failed { actual_value, .. } = failed else { panic!("json-contains should fail") };
+        assert!(actual_value.ends_with("; first difference: $.user.role: \"admin\" instead of \"owner\""), "{actual_value}");
+
+        let error = evaluate_single_condition_test(&condition("{role: admin}"), &response).unwrap_err();
+        assert!(error.to_string().contains("Expected value of json-contains is not valid JSON"), "{error}");
     }
-    
+
     #[test]
-    fn test_evaluate_json_path_condition() {
-        let response = create_test_response();
-        let condition = Condition {
+    fn test_type_equals() {
+        let condition = |key: ConditionKey, value: &str| Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
-            key: ConditionKey::ResponseBodyPath("id".to_string()),
-            operator: Operator::Equals,
+            key,
+            operator: Operator::TypeEquals,
             case_sensitive: false,
-            value: "123".to_string(),
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result);
+        let path = |path: &str| ConditionKey::ResponseBodyPath(path.to_string());
+        let response = Response {
+            body: r#"{"id": "123", "count": 2, "tags": [], "owner": null, "active": true}"#.to_string(),
+            ..Response::default()
+        };
+
+        for (key, expected) in [(path("count"), "number"), (path("tags"), "array"), (path("owner"), "null"), (path("active"), "Boolean"), (path("tags | length"), "number"), (ConditionKey::ResponseBody, "object")] {
+            assert!(evaluate_single_condition_test(&condition(key, expected), &response).unwrap(), "{expected}");
+        }
+
+        let failed = evaluate_conditions(&[condition(path("id"), "number")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed, ["response-body.id type-equals 'number' (actual: 'string')"]);
+
+        let error = evaluate_single_condition_test(&condition(path("id"), "integer"), &response).unwrap_err();
+        assert!(error.to_string().contains("type-equals expects one of string, number, boolean, array, object, null"), "{error}");
     }
-    
+
     #[test]
-    fn test_evaluate_not_condition() {
-        let response = create_test_response();
+    fn test_json_equals_invalid_json() {
         let condition = Condition {
             is_or: false,
-            is_not: true,
-            key: ConditionKey::StatusCode,
-            operator: Operator::Equals,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBody,
+            operator: Operator::JsonEquals,
             case_sensitive: false,
-            value: "404".to_string(),
+            value: "{\"id\": 1}".to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result); // NOT 404 should be true for 200
+
+        assert!(!evaluate_single_condition_test(&condition, &Response { body: "<html>".to_string(), ..Response::default() }).unwrap());
+        let invalid_expected = Condition { value: "{id: 1}".to_string(), ..condition };
+        let error = evaluate_single_condition_test(&invalid_expected, &create_test_response()).unwrap_err();
+        assert!(error.to_string().contains("Expected value of json-equals is not valid JSON"), "{error}");
     }
-    
+
     #[test]
-    fn test_exists_operator_with_existing_field() {
-        let response = create_test_response();
-        let condition = Condition {
+    fn test_equals_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let greq_path = dir.path().join("users.greq").display().to_string();
+        fs::write(dir.path().join("expected.txt"), "line one\nline two\n").unwrap();
+        fs::write(dir.path().join("expected.json"), "{\"tags\": [\"a\"], \"id\": 7}").unwrap();
+        let condition = |operator: Operator, value: &str| Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
-            key: ConditionKey::ResponseBodyPath("id".to_string()),
-            operator: Operator::Exists,
+            key: ConditionKey::ResponseBody,
+            operator,
             case_sensitive: false,
-            value: "true".to_string(),
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result); // id exists in the response
+        let response = |body: &str| Response { body: body.to_string(), ..Response::default() };
+        let evaluate = |condition: &Condition, body: &str| {
+            evaluate_single_condition_with_details(condition, &response(body), &greq_path, &Header::default())
+        };
+
+        assert!(matches!(evaluate(&condition(Operator::EqualsFile, "expected.txt"), "line one\nline two").unwrap(), ConditionResult::Passed { .. }));
+        assert!(matches!(evaluate(&condition(Operator::JsonEqualsFile, "expected.json"), r#"{"id":7,"tags":["a"]}"#).unwrap(), ConditionResult::Passed { .. }));
+
+        let ConditionResult::Failed { actual_value, .. } = evaluate(&condition(Operator::EqualsFile, "expected.txt"), "line one\nline 2").unwrap() else {
+            panic!("equals-file should fail");
+        };
+        assert!(actual_value.ends_with("first difference at line 2: expected 'line two', got 'line 2'"), "{actual_value}");
+
+        let error = evaluate(&condition(Operator::EqualsFile, "missing.txt"), "").unwrap_err();
+        assert!(error.to_string().contains("Cannot read fixture file 'missing.txt'"), "{error}");
     }
-    
+
     #[test]
-    fn test_exists_operator_with_missing_field() {
-        let response = create_test_response();
-        let condition = Condition {
+    fn test_contains_any() {
+        let response = Response {
+            body: r#"{"error": "NullPointerException at Handler.java:42", "trace": "StackTrace omitted"}"#.to_string(),
+            ..Response::default()
+        };
+        let condition = |is_not: bool, case_sensitive: bool, value: &str| Condition {
             is_or: false,
-            is_not: false,
-            key: ConditionKey::ResponseBodyPath("nonexistent".to_string()),
-            operator: Operator::Exists,
-            case_sensitive: false,
-            value: "false".to_string(),
+            is_and: false,
+            is_not,
+            key: ConditionKey::ResponseBody,
+            operator: Operator::ContainsAny,
+            case_sensitive,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result); // nonexistent field should have exists: false
+
+        assert!(evaluate_single_condition_test(&condition(false, false, "password, stacktrace"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition(false, true, "password, stacktrace"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition(true, false, "password, secret"), &response).unwrap());
+
+        let failed = evaluate_conditions(&[condition(true, false, "password, secret, stacktrace")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].contains("found 'stacktrace'"), "{}", failed[0]);
+
+        let error = evaluate_single_condition_test(&condition(false, false, " , "), &response).unwrap_err();
+        assert!(error.to_string().contains("contains-any expects a comma-separated list"), "{error}");
     }
-    
+
     #[test]
-    fn test_not_exists_operator_with_missing_field() {
-        let response = create_test_response();
-        // This is the bug fix test: "not response-body.data exists: true"
-        // When data field doesn't exist, this should PASS
+    fn test_equals_binary_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let greq_path = dir.path().join("image.greq").display().to_string();
+        let png = vec![0x89, b'P', b'N', b'G', 0xff, 0x00, 0xfe];
+        fs::write(dir.path().join("expected.bin"), &png).unwrap();
         let condition = Condition {
             is_or: false,
-            is_not: true,
-            key: ConditionKey::ResponseBodyPath("data".to_string()),
-            operator: Operator::Exists,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBody,
+            operator: Operator::EqualsBinaryFile,
             case_sensitive: false,
-            value: "true".to_string(),
+            value: "expected.bin".to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(result); // NOT exists:true should be true when field is missing
+        // The text body is lossy, only the raw bytes match the fixture
+        let response = |raw: Vec<u8>| Response {
+            body: String::from_utf8_lossy(&raw).into_owned(),
+            raw_body: Some(raw),
+            ..Response::default()
+        };
+        let evaluate = |raw: Vec<u8>| evaluate_single_condition_with_details(&condition, &response(raw), &greq_path, &Header::default());
+
+        assert!(matches!(evaluate(png.clone()).unwrap(), ConditionResult::Passed { .. }));
+
+        let mut changed = png.clone();
+        changed[5] = 0x01;
+        let ConditionResult::Failed { actual_value, .. } = evaluate(changed).unwrap() else {
+            panic!("equals-binary-file should fail");
+        };
+        assert_eq!(actual_value, "7 bytes; first difference at byte 5: 0x01 instead of 0x00");
+
+        let ConditionResult::Failed { actual_value, .. } = evaluate(png[..4].to_vec()).unwrap() else {
+            panic!("a truncated body should fail");
+        };
+        assert_eq!(actual_value, "4 bytes; ends at byte 4, the file has 7 bytes");
     }
-    
+
     #[test]
-    fn test_not_exists_operator_with_existing_field() {
-        let response = create_test_response();
-        // When field exists, "not exists: true" should FAIL
-        let condition = Condition {
+    fn test_array_order_operators() {
+        let condition = |path: &str, operator: Operator, value: &str| Condition {
             is_or: false,
-            is_not: true,
-            key: ConditionKey::ResponseBodyPath("id".to_string()),
-            operator: Operator::Exists,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath(path.to_string()),
+            operator,
             case_sensitive: false,
-            value: "true".to_string(),
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
         };
-        
-        let result = evaluate_single_condition_test(&condition, &response).unwrap();
-        assert!(!result); // NOT exists:true should be false when field exists
-    }
+        let response = Response {
+            body: r#"{"ids": [2, 10, 10, 35], "scores": [9, 7, 8], "tags": ["a", "B", "c"]}"#.to_string(),
+            ..Response::default()
+        };
+        let failure = |condition: &Condition| match evaluate_single_condition_with_details(condition, &response, "test.greq", &Header::default()).unwrap() {
+            ConditionResult::Failed { actual_value, .. } => actual_value,
+            ConditionResult::Passed { .. } => panic!("{condition:?} should fail"),
+        };
+
+        assert!(evaluate_single_condition_test(&condition("ids", Operator::SortedAscending, "true"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("scores", Operator::SortedAscending, "false"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("tags", Operator::SortedAscending, "true"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("tags", Operator::EqualsSequence, "a, b, c"), &response).unwrap());
+
+        assert!(failure(&condition("scores", Operator::SortedDescending, "true")).ends_with("first out-of-order element at index 2: 8 after 7"));
+        assert!(failure(&condition("tags", Operator::EqualsSequence, "a,c,b")).ends_with("first difference at index 1: expected 'c', got 'B'"));
+        assert!(failure(&condition("tags", Operator::EqualsSequence, "a,b")).ends_with("unexpected element 'c' at index 2"));
+        assert!(failure(&condition("tags", Operator::EqualsSequence, "a,b,c,d")).ends_with("missing 'd' at index 3"));
+
+        let error = evaluate_single_condition_test(&condition("ids[0]", Operator::SortedAscending, "true"), &response).unwrap_err();
+        assert!(error.to_string().contains("sorted-ascending needs a JSON array, got '2'"), "{error}");
+    }
+
+    #[test]
+    fn test_update_fixture_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let greq_path = dir.path().join("users.greq").display().to_string();
+        fs::write(dir.path().join("expected.json"), "{\"id\": 1}").unwrap();
+        let conditions = vec![
+            Condition {
+                is_or: false,
+                is_and: false,
+                is_not: false,
+                key: ConditionKey::ResponseBody,
+                operator: Operator::JsonEqualsFile,
+                case_sensitive: false,
+                value: "expected.json".to_string(),
+                name: None,
+                environments: Vec::new(),
+            },
+            Condition {
+                is_or: false,
+                is_and: false,
+                is_not: false,
+                key: ConditionKey::ResponseBodyPath("id".to_string()),
+                operator: Operator::EqualsFile,
+                case_sensitive: false,
+                value: "new-id.txt".to_string(),
+                name: None,
+                environments: Vec::new(),
+            },
+        ];
+        let response = Response { body: r#"{"id":2}"#.to_string(), ..Response::default() };
+
+        let updated = update_fixture_files(&conditions, &response, &greq_path, &Header::default()).unwrap();
+
+        assert_eq!(updated.len(), 2);
+        assert_eq!(fs::read_to_string(dir.path().join("expected.json")).unwrap(), "{\n  \"id\": 2\n}\n");
+        assert_eq!(fs::read_to_string(dir.path().join("new-id.txt")).unwrap(), "2");
+        assert!(update_fixture_files(&conditions, &response, &greq_path, &Header::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_body_transforms() {
+        let response = Response {
+            body: r#"{"data": "eyJ1c2VyIjogIkFubiJ9", "next": "%2Fusers%3Fpage%3D2", "meta": "{\"count\": 3}"}"#.to_string(),
+            ..Response::default()
+        };
+        let transform = |path: &str, kind: TransformKind| BodyTransform { path: path.to_string(), kind };
+        let transforms = vec![
+            transform("data", TransformKind::Base64Decode),
+            transform("data", TransformKind::JsonParse),
+            transform("next", TransformKind::UrlDecode),
+            transform("meta", TransformKind::JsonParse),
+        ];
+
+        let transformed = apply_body_transforms(&response, &transforms, "test.greq").unwrap();
+
+        assert_eq!(transformed.get_path("data.user"), Some(Value::String("Ann".to_string())));
+        assert_eq!(transformed.get_path("next"), Some(Value::String("/users?page=2".to_string())));
+        assert_eq!(transformed.get_path("meta.count"), Some(Value::from(3)));
+        assert!(response.get_path("data.user").is_none(), "The original response must not change");
+
+        let error = apply_body_transforms(&response, &[transform("meta", TransformKind::Base64Decode)], "test.greq").unwrap_err();
+        assert!(error.to_string().contains("test.greq: transform response-body.meta: invalid base64"), "{error}");
+    }
+
+    #[test]
+    fn test_whole_body_transform() {
+        let response = Response { body: "aGVsbG8gd29ybGQ=".to_string(), ..Response::default() };
+
+        let transformed = apply_body_transforms(&response, &[BodyTransform { path: String::new(), kind: TransformKind::Base64Decode }], "test.greq").unwrap();
+
+        assert_eq!(transformed.body, "hello world");
+    }
+
+    #[test]
+    fn test_and_group_failure_context() {
+        let condition = |is_or: bool, is_and: bool, key: ConditionKey, value: &str| Condition {
+            is_or,
+            is_and,
+            is_not: false,
+            key,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let response = create_test_response();
+        let conditions = vec![
+            condition(false, false, ConditionKey::StatusCode, "200"),
+            condition(false, true, ConditionKey::ResponseBodyPath("name".to_string()), "test"),
+            condition(false, true, ConditionKey::ResponseBodyPath("id".to_string()), "7"),
+            condition(true, false, ConditionKey::ResponseBodyPath("id".to_string()), "8"),
+        ];
+
+        let failures = evaluate_conditions(&conditions, &response, "test.greq").unwrap();
+
+        assert_eq!(
+            failures[0],
+            "response-body.id equals '7' (actual: '123') (condition 3 of 3 in and group: status-code equals '200' and response-body.name equals 'test' and response-body.id equals '7' or response-body.id equals '8')"
+        );
+        assert_eq!(
+            evaluate_conditions(&conditions[2..], &response, "test.greq").unwrap()[0],
+            "response-body.id equals '7' (actual: '123')"
+        );
+    }
+
+    #[test]
+    fn test_numeric_header_comparisons() {
+        let header_condition = |name: &str, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Header(name.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.headers.insert("content-length".to_string(), "1536".to_string());
+        response.headers.insert("age".to_string(), "90".to_string());
+        response.headers.insert("retry-after".to_string(), "Wed, 21 Oct 2026 07:28:00 GMT".to_string());
+
+        assert!(evaluate_single_condition_test(&header_condition("content-length", Operator::GreaterThan, "1024"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&header_condition("content-length", Operator::GreaterThan, "1KB"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&header_condition("content-length", Operator::LessThanOrEqual, "1.5kb"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&header_condition("content-length", Operator::GreaterThan, "1MB"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&header_condition("age", Operator::LessThan, "120"), &response).unwrap());
+
+        let error = evaluate_single_condition_test(&header_condition("retry-after", Operator::GreaterThan, "10"), &response).unwrap_err();
+        assert!(error.to_string().contains("Header 'retry-after' has the non-numeric value"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("512"), Some(512));
+        assert_eq!(parse_byte_size("2 MB"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_byte_size("0.5GB"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_byte_size("1TB"), None);
+    }
+
+    #[test]
+    fn test_format_print_values() {
+        let response = create_test_response();
+        let keys = vec![
+            ConditionKey::ResponseBodyPath("name".to_string()),
+            ConditionKey::ResponseBodyPath("missing".to_string()),
+            ConditionKey::StatusCode,
+        ];
+
+        let lines = format_print_values(&keys, &response, "debug.greq", &Header::default());
+
+        assert_eq!(lines[0], "debug.greq: response-body.name = test");
+        assert!(lines[1].starts_with("debug.greq: response-body.missing = <"), "{}", lines[1]);
+        assert_eq!(lines[2], "debug.greq: status-code = 200");
+    }
+    
+    #[test]
+    fn test_evaluate_status_code_condition() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::StatusCode,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "200".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result);
+    }
+    
+    #[test]
+    fn test_evaluate_response_body_contains() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBody,
+            operator: Operator::Contains,
+            case_sensitive: false,
+            value: "test".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result);
+    }
+    
+    #[test]
+    fn test_evaluate_json_path_condition() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath("id".to_string()),
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "123".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result);
+    }
+    
+    #[test]
+    fn test_evaluate_not_condition() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: true,
+            key: ConditionKey::StatusCode,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "404".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result); // NOT 404 should be true for 200
+    }
+    
+    #[test]
+    fn test_exists_operator_with_existing_field() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath("id".to_string()),
+            operator: Operator::Exists,
+            case_sensitive: false,
+            value: "true".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result); // id exists in the response
+    }
+    
+    #[test]
+    fn test_exists_operator_with_missing_field() {
+        let response = create_test_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath("nonexistent".to_string()),
+            operator: Operator::Exists,
+            case_sensitive: false,
+            value: "false".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result); // nonexistent field should have exists: false
+    }
+    
+    #[test]
+    fn test_not_exists_operator_with_missing_field() {
+        let response = create_test_response();
+        // This is the bug fix test: "not response-body.data exists: true"
+        // When data field doesn't exist, this should PASS
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: true,
+            key: ConditionKey::ResponseBodyPath("data".to_string()),
+            operator: Operator::Exists,
+            case_sensitive: false,
+            value: "true".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(result); // NOT exists:true should be true when field is missing
+    }
+    
+    #[test]
+    fn test_not_exists_operator_with_existing_field() {
+        let response = create_test_response();
+        // When field exists, "not exists: true" should FAIL
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: true,
+            key: ConditionKey::ResponseBodyPath("id".to_string()),
+            operator: Operator::Exists,
+            case_sensitive: false,
+            value: "true".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        
+        let result = evaluate_single_condition_test(&condition, &response).unwrap();
+        assert!(!result); // NOT exists:true should be false when field exists
+    }
     
     #[test]
     fn test_exists_false_with_missing_field() {
@@ -528,11 +2135,14 @@ mod tests {
         // When field doesn't exist, this should PASS
         let condition = Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
             key: ConditionKey::ResponseBodyPath("nonexistent_field".to_string()),
             operator: Operator::Exists,
             case_sensitive: false,
             value: "false".to_string(),
+            name: None,
+            environments: Vec::new(),
         };
         
         let result = evaluate_single_condition_test(&condition, &response).unwrap();
@@ -545,14 +2155,783 @@ mod tests {
         // When field exists, "exists: false" should FAIL
         let condition = Condition {
             is_or: false,
+            is_and: false,
             is_not: false,
             key: ConditionKey::ResponseBodyPath("id".to_string()),
             operator: Operator::Exists,
             case_sensitive: false,
             value: "false".to_string(),
+            name: None,
+            environments: Vec::new(),
         };
         
         let result = evaluate_single_condition_test(&condition, &response).unwrap();
         assert!(!result); // exists:false should be false when field exists
     }
+
+    #[test]
+    fn test_wildcard_exists_sweeps_array() {
+        let response = Response {
+            body: r#"{"items": [{"id": 1, "name": "a"}, {"id": 2}, {"name": "c"}], "count": 3}"#.to_string(),
+            ..Response::default()
+        };
+        let condition = |path: &str, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath(path.to_string()),
+            operator: Operator::Exists,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+
+        let failed = evaluate_conditions(&[condition("items[*].id", "true")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed, ["response-body.items[*].id exists 'true' (actual: 'response-body.items[2].id is missing')"]);
+        assert!(evaluate_single_condition_test(&condition("items[*]", "true"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("items[*].password", "false"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition("items[*].name", "false"), &response).unwrap());
+
+        let error = evaluate_single_condition_test(&condition("count[*].id", "true"), &response).unwrap_err();
+        assert!(error.to_string().contains("Wildcard '[*]' expects an array at 'count', found a number"), "{error}");
+    }
+
+    fn piped_condition(path: &str, operator: Operator, value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath(path.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_json_pipe_functions() {
+        let response = Response {
+            body: r#"{"users": [{"name": "Ann"}, {"name": "Bob"}], "meta": {"page": 1, "total": 2}}"#.to_string(),
+            ..Response::default()
+        };
+
+        let checks = [
+            piped_condition("users | length", Operator::GreaterThan, "0"),
+            piped_condition("users | length", Operator::Equals, "2"),
+            piped_condition("users | first | keys", Operator::Equals, r#"["name"]"#),
+            piped_condition("users | last", Operator::Contains, "Bob"),
+            piped_condition("meta | keys", Operator::Equals, r#"["page","total"]"#),
+            piped_condition("meta | values | length", Operator::Equals, "2"),
+            piped_condition("| length", Operator::Equals, "2"),
+        ];
+
+        for condition in &checks {
+            assert!(evaluate_single_condition_test(condition, &response).unwrap(), "Failed: {:?}", condition.key);
+        }
+    }
+
+    #[test]
+    fn test_json_pipe_function_type_error() {
+        let response = Response {
+            body: r#"{"count": 5}"#.to_string(),
+            ..Response::default()
+        };
+        let condition = piped_condition("count | first", Operator::Equals, "5");
+
+        let result = evaluate_single_condition_test(&condition, &response);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Cannot apply 'first' to a number"));
+    }
+
+    #[test]
+    fn test_large_integer_ids_compare_exactly() {
+        let response = Response {
+            body: r#"{"id": 1234567890123456789, "next_id": 9007199254740993}"#.to_string(),
+            ..Response::default()
+        };
+
+        // Both values are beyond 2^53 and would be rounded by an f64 conversion
+        let checks = [
+            (piped_condition("id", Operator::Equals, "1234567890123456789"), true),
+            (piped_condition("id", Operator::Equals, "1234567890123456788"), false),
+            (piped_condition("next_id", Operator::GreaterThan, "9007199254740992"), true),
+            (piped_condition("next_id", Operator::LessThanOrEqual, "9007199254740992"), false),
+        ];
+
+        for (condition, expected) in &checks {
+            assert_eq!(evaluate_single_condition_test(condition, &response).unwrap(), *expected, "{:?} {}", condition.operator, condition.value);
+        }
+    }
+
+    #[test]
+    fn test_padded_numbers_compare_as_strings() {
+        assert!(!compare_equals("007", "7", true));
+        assert!(!compare_equals("+1", "1", true));
+        assert!(compare_equals("007", "007", true));
+        assert!(compare_equals("-12", "-12", true));
+        assert_eq!(canonical_integer("-0"), Some(0));
+        assert_eq!(canonical_integer("0123"), None);
+    }
+
+    fn charset_condition(value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Charset,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_charset_from_content_type() {
+        let mut response = create_test_response();
+        response.headers.insert("content-type".to_string(), "text/html; Charset=\"ISO-8859-1\"".to_string());
+
+        assert!(evaluate_single_condition_test(&charset_condition("iso-8859-1"), &response).unwrap());
+
+        let failed = evaluate_conditions(&[charset_condition("utf-8")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].contains("iso-8859-1"), "Failure should show the detected charset: {}", failed[0]);
+    }
+
+    #[test]
+    fn test_charset_fallback_without_parameter() {
+        let response = create_test_response();
+
+        assert!(evaluate_single_condition_test(&charset_condition("utf-8"), &response).unwrap());
+    }
+
+    #[test]
+    fn test_content_language_and_vary() {
+        let response = Response {
+            headers: HashMap::from([("content-language".to_string(), "fr-FR".to_string()), ("vary".to_string(), "Accept-Language".to_string())]),
+            all_headers: vec![
+                ("content-language".to_string(), "fr-FR".to_string()),
+                ("vary".to_string(), "Accept-Encoding".to_string()),
+                ("vary".to_string(), "Accept-Language".to_string()),
+            ],
+            ..Response::default()
+        };
+        let condition = |key: ConditionKey, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key,
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+
+        assert!(evaluate_single_condition_test(&condition(ConditionKey::ContentLanguage, Operator::Equals, "fr-FR"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition(ConditionKey::Vary, Operator::Equals, "Accept-Encoding, Accept-Language"), &response).unwrap());
+
+        let failed = evaluate_conditions(&[condition(ConditionKey::ContentLanguage, Operator::Equals, "de-DE")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed, ["content-language equals 'de-DE' (actual: 'fr-FR')"]);
+    }
+
+    fn elements_condition(quantifier: Quantifier, element_path: &str, operator: Operator, value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyElements {
+                path: "items".to_string(),
+                quantifier,
+                element_path: element_path.to_string(),
+            },
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_any_and_all_array_elements() {
+        let mut response = create_test_response();
+        response.body = r#"{"items": [{"status": "active", "age": 3}, {"status": "inactive", "age": 9}, {"age": 5}]}"#.to_string();
+        let check = |condition: Condition| evaluate_single_condition_test(&condition, &response).unwrap();
+
+        assert!(check(elements_condition(Quantifier::Any, "status", Operator::Equals, "active")));
+        assert!(!check(elements_condition(Quantifier::All, "status", Operator::Equals, "active")));
+        assert!(check(elements_condition(Quantifier::All, "age", Operator::LessThan, "10")));
+        assert!(check(elements_condition(Quantifier::Any, "status", Operator::Exists, "false")));
+        assert!(!check(elements_condition(Quantifier::All, "status", Operator::Exists, "true")));
+
+        let failed = evaluate_conditions(&[elements_condition(Quantifier::All, "status", Operator::Equals, "active")], &response, "test-file.greq").unwrap();
+        assert!(
+            failed[0].contains("response-body.items all .status equals 'active' (actual: '[active, inactive, <missing>]')"),
+            "{}", failed[0]
+        );
+    }
+
+    #[test]
+    fn test_any_on_non_array_errors() {
+        let mut response = create_test_response();
+        response.body = r#"{"items": {"status": "active"}}"#.to_string();
+
+        let error = evaluate_single_condition_test(&elements_condition(Quantifier::Any, "status", Operator::Equals, "active"), &response)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("'response-body.items' is an object, expected an array for any/all"), "{error}");
+    }
+
+    #[test]
+    fn test_cache_status_across_providers() {
+        let status = |headers: &[(&str, &str)], header: &Header| {
+            let response = Response {
+                headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                ..Response::default()
+            };
+            detect_cache_status(&response, header)
+        };
+        let default = Header::default();
+
+        assert_eq!(status(&[("x-cache", "Hit from cloudfront")], &default), "hit");
+        assert_eq!(status(&[("x-cache", "Miss from cloudfront")], &default), "miss");
+        assert_eq!(status(&[("x-cache", "HIT, MISS")], &default), "miss");
+        assert_eq!(status(&[("cf-cache-status", "REVALIDATED")], &default), "hit");
+        assert_eq!(status(&[("cf-cache-status", "DYNAMIC")], &default), "miss");
+        assert_eq!(status(&[("cache-status", "Origin; fwd=uri-miss, CDN; hit")], &default), "hit");
+        assert_eq!(status(&[("age", "120")], &default), "hit");
+        assert_eq!(status(&[("age", "0")], &default), "miss");
+        assert_eq!(status(&[("content-type", "text/html")], &default), "");
+
+        // Recognized headers take precedence over age, and cache-header replaces them
+        assert_eq!(status(&[("x-cache", "MISS"), ("age", "30")], &default), "miss");
+        let custom = Header { cache_header: Some("x-edge-result".to_string()), ..Header::default() };
+        assert_eq!(status(&[("x-cache", "MISS"), ("x-edge-result", "TCP_HIT")], &custom), "hit");
+    }
+
+    #[test]
+    fn test_tls_certificate_conditions() {
+        let condition = |property: &str, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Tls(property.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.tls_certificate = Some(PeerCertificate {
+            subject: "CN=api.example.com".to_string(),
+            issuer: "C=US, O=Let's Encrypt, CN=R3".to_string(),
+            not_after: Utc::now() + chrono::Duration::days(45) + chrono::Duration::hours(1),
+        });
+
+        assert!(evaluate_single_condition_test(&condition("days-until-expiry", Operator::Equals, "45"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("issuer", Operator::Contains, "let's encrypt"), &response).unwrap());
+
+        response.tls_certificate = None;
+        assert!(evaluate_single_condition_test(&condition("issuer", Operator::Exists, "false"), &response).unwrap());
+        let error = evaluate_single_condition_test(&condition("days-until-expiry", Operator::GreaterThan, "30"), &response).unwrap_err();
+        assert!(error.to_string().contains("tls.days-until-expiry is not available, the request was not made over HTTPS"), "{error}");
+
+        response.tls_certificate = Some(PeerCertificate {
+            subject: "CN=api.example.com".to_string(),
+            issuer: "CN=R3".to_string(),
+            not_after: Utc::now(),
+        });
+        response.tls_version = Some("1.2".to_string());
+        assert!(evaluate_single_condition_test(&condition("version", Operator::GreaterThanOrEqual, "1.2"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition("version", Operator::GreaterThanOrEqual, "1.3"), &response).unwrap());
+    }
+
+    #[test]
+    fn test_explain_evaluates_every_condition() {
+        let condition = |key: ConditionKey, operator: Operator, value: &str, is_or: bool| Condition {
+            is_or,
+            is_and: false,
+            is_not: false,
+            key,
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let conditions = vec![
+            condition(ConditionKey::StatusCode, Operator::Equals, "201", false),
+            condition(ConditionKey::StatusCode, Operator::Equals, "200", true),
+            condition(ConditionKey::ResponseBodyPath("missing".to_string()), Operator::Equals, "1", false),
+            condition(ConditionKey::Header("content-type".to_string()), Operator::Contains, "json", false),
+        ];
+
+        let explanations = explain_conditions(&conditions, &create_test_response(), "test.greq", &Header::default());
+        let lines: Vec<String> = explanations.iter().map(format_explanation).collect();
+
+        assert_eq!(lines[0], "FAIL status-code equals '201' (actual: '200')");
+        assert_eq!(lines[1], "PASS or status-code equals '200' (actual: '200')");
+        assert!(lines[2].starts_with("FAIL response-body.missing equals '1' (error: "), "{}", lines[2]);
+        assert_eq!(lines[3], "PASS headers.content-type contains 'json' (actual: 'application/json')");
+    }
+
+    #[test]
+    fn test_single_pass_reports_the_same_failures() {
+        let condition = |key: ConditionKey, value: &str, is_or: bool, is_and: bool| Condition {
+            is_or,
+            is_and,
+            is_not: false,
+            key,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let response = create_test_response();
+        let missing = || ConditionKey::ResponseBodyPath("missing".to_string());
+        let cases = [
+            vec![condition(ConditionKey::StatusCode, "200", false, false)],
+            vec![condition(ConditionKey::StatusCode, "201", false, false), condition(ConditionKey::StatusCode, "200", true, false)],
+            vec![
+                condition(ConditionKey::StatusCode, "200", false, false),
+                condition(ConditionKey::StatusCode, "404", false, true),
+                condition(missing(), "1", false, false),
+            ],
+            vec![condition(missing(), "1", false, false), condition(ConditionKey::StatusCode, "201", false, false)],
+        ];
+
+        for conditions in cases {
+            let (explanations, single_pass) = evaluate_and_explain_conditions(&conditions, &response, "test.greq", &Header::default());
+            let short_circuit = evaluate_conditions_with_header(&conditions, &response, "test.greq", &Header::default());
+            assert_eq!(explanations.len(), conditions.len());
+            match (single_pass, short_circuit) {
+                (Ok(single_pass), Ok(short_circuit)) => assert_eq!(single_pass, short_circuit),
+                (Err(single_pass), Err(short_circuit)) => assert_eq!(single_pass.to_string(), short_circuit.to_string()),
+                (single_pass, short_circuit) => panic!("{single_pass:?} != {short_circuit:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_timing_phase_conditions() {
+        let condition = |phase: &str, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Time(phase.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.timings = Some(ResponseTimings {
+            connect: Duration::from_millis(12),
+            ttfb: Duration::from_micros(180_900),
+            download: Duration::from_millis(40),
+        });
+
+        assert!(evaluate_single_condition_test(&condition("ttfb", Operator::Equals, "180"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("connect", Operator::LessThan, "50"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("download", Operator::GreaterThan, "30"), &response).unwrap());
+
+        response.timings = None;
+        assert!(evaluate_single_condition_test(&condition("ttfb", Operator::Exists, "false"), &response).unwrap());
+        let error = evaluate_single_condition_test(&condition("ttfb", Operator::LessThan, "200"), &response).unwrap_err();
+        assert!(error.to_string().contains("time.ttfb is unavailable"), "{error}");
+    }
+
+    #[test]
+    fn test_location_query_param() {
+        let condition = |param: &str, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::LocationQuery(param.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.headers.insert(
+            "location".to_string(),
+            "https://app.example.com/callback?code=a%2Fb+c&state=xyz&empty=#state=fragment".to_string(),
+        );
+
+        assert!(evaluate_single_condition_test(&condition("code", Operator::Equals, "a/b c"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("state", Operator::Equals, "xyz"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("empty", Operator::Equals, ""), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("error", Operator::Exists, "false"), &response).unwrap());
+    }
+
+    #[test]
+    fn test_trailer_conditions() {
+        let condition = |name: &str, operator: Operator, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Trailer(name.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.trailers = Some(HashMap::from([("grpc-status".to_string(), "0".to_string())]));
+
+        assert!(evaluate_single_condition_test(&condition("grpc-status", Operator::Equals, "0"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("grpc-message", Operator::Exists, "false"), &response).unwrap());
+
+        response.trailers = None;
+        assert!(evaluate_single_condition_test(&condition("grpc-status", Operator::Exists, "false"), &response).unwrap());
+        let error = evaluate_single_condition_test(&condition("grpc-status", Operator::Equals, "0"), &response).unwrap_err();
+        assert!(error.to_string().contains("trailers.grpc-status is unavailable"), "{error}");
+    }
+
+    fn header_count_condition(operator: Operator, value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::HeaderCount,
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_header_count() {
+        let mut response = create_test_response();
+        response.headers.insert("x-frame-options".to_string(), "DENY".to_string());
+
+        assert!(evaluate_single_condition_test(&header_count_condition(Operator::Equals, "2"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&header_count_condition(Operator::GreaterThanOrEqual, "2"), &response).unwrap());
+
+        let failed = evaluate_conditions(&[header_count_condition(Operator::GreaterThan, "5")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(
+            failed[0].contains("(actual: '2; present: content-type, x-frame-options')"),
+            "Failure should list the received headers: {}", failed[0]
+        );
+    }
+
+    #[test]
+    fn test_multipart_parts() {
+        let condition = |key: ConditionKey, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key,
+            operator: Operator::Equals,
+            case_sensitive: true,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.headers.insert("content-type".to_string(), "multipart/mixed; boundary=\"batch_42\"".to_string());
+        response.body = [
+            "preamble",
+            "--batch_42",
+            "Content-Type: application/http",
+            "",
+            "HTTP/1.1 201 Created",
+            "--batch_42  ",
+            "",
+            "no headers",
+            "--batch_42--",
+            "epilogue",
+        ].join("\r\n");
+
+        assert!(evaluate_single_condition_test(&condition(ConditionKey::PartBody(0), "HTTP/1.1 201 Created"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition(ConditionKey::PartHeader(0, "content-type".to_string()), "application/http"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition(ConditionKey::PartBody(1), "no headers"), &response).unwrap());
+
+        let error = evaluate_single_condition_test(&condition(ConditionKey::PartBody(2), ""), &response).unwrap_err();
+        assert!(error.to_string().contains("part[2] does not exist, the multipart response has 2 parts"), "{error}");
+
+        response.headers.insert("content-type".to_string(), "application/json".to_string());
+        let error = evaluate_single_condition_test(&condition(ConditionKey::PartBody(0), ""), &response).unwrap_err();
+        assert!(error.to_string().contains("part[0] needs a multipart response with a boundary, content-type is 'application/json'"), "{error}");
+    }
+
+    #[test]
+    fn test_from_cache() {
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::FromCache,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "false".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        assert!(evaluate_single_condition_test(&condition, &response).unwrap());
+
+        response.from_cache = true;
+        assert!(!evaluate_single_condition_test(&condition, &response).unwrap());
+    }
+
+    #[test]
+    fn test_header_occurrences() {
+        let condition = |name: &str, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::HeaderOccurrences(name.to_string()),
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let mut response = create_test_response();
+        response.all_headers = vec![
+            ("content-type".to_string(), "application/json".to_string()),
+            ("set-cookie".to_string(), "session=abc".to_string()),
+            ("set-cookie".to_string(), "theme=dark".to_string()),
+        ];
+
+        assert!(evaluate_single_condition_test(&condition("set-cookie", "2"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("content-type", "1"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("etag", "0"), &response).unwrap());
+
+        let failed = evaluate_conditions(&[condition("set-cookie", "1")], &response, "test-file.greq").unwrap();
+        assert_eq!(failed, ["header-count.set-cookie equals '1' (actual: '2; values: session=abc | theme=dark')"]);
+    }
+
+    fn latency_within(value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Latency,
+            operator: Operator::Within,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_within_tolerance() {
+        // create_test_response has a latency of 150ms
+        let response = create_test_response();
+
+        assert!(evaluate_single_condition_test(&latency_within("100±50"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&latency_within("200 +/- 50"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&latency_within("500±50"), &response).unwrap());
+    }
+
+    #[test]
+    fn test_within_malformed_tolerance() {
+        let response = create_test_response();
+
+        for expression in ["150", "150±", "abc±10", "150±-5"] {
+            let result = evaluate_single_condition_test(&latency_within(expression), &response);
+            assert!(result.unwrap_err().to_string().contains("Invalid tolerance"), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_approx_equals() {
+        let response = Response {
+            body: r#"{"price": 19.994, "total": 0.30000000000000004, "name": "pen"}"#.to_string(),
+            ..Response::default()
+        };
+        let condition = |path: &str, value: &str| Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ResponseBodyPath(path.to_string()),
+            operator: Operator::ApproxEquals,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+
+        assert!(evaluate_single_condition_test(&condition("price", "19.99±0.01"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition("price", "19.98 +/- 0.001"), &response).unwrap());
+        assert!(evaluate_single_condition_test(&condition("total", "0.3"), &response).unwrap());
+        assert!(!evaluate_single_condition_test(&condition("price", "19.99"), &response).unwrap());
+
+        let error = evaluate_single_condition_test(&condition("name", "1±1"), &response).unwrap_err();
+        assert!(error.to_string().contains("'pen', it is not a number"), "{error}");
+        let error = evaluate_single_condition_test(&condition("price", "cheap"), &response).unwrap_err();
+        assert!(error.to_string().contains("got 'cheap'"), "{error}");
+    }
+
+    #[test]
+    fn test_scheme_of_final_url() {
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Scheme,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "https".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+        let downgraded = Response {
+            final_url: Some("http://example.com/login".to_string()),
+            ..Response::default()
+        };
+        let secure = Response {
+            final_url: Some("https://example.com/login".to_string()),
+            ..Response::default()
+        };
+
+        assert!(evaluate_single_condition_test(&condition, &secure).unwrap());
+        assert!(!evaluate_single_condition_test(&condition, &downgraded).unwrap());
+        let error = evaluate_single_condition_test(&condition, &Response::default()).unwrap_err();
+        assert!(error.to_string().contains("scheme is unavailable"), "{error}");
+    }
+
+    fn create_error_envelope_response() -> Response {
+        Response {
+            status_code: 404,
+            body: r#"{"error": {"code": 5, "message": "Resource not found"}}"#.to_string(),
+            ..Response::default()
+        }
+    }
+
+    #[test]
+    fn test_error_envelope_keys() {
+        let response = create_error_envelope_response();
+        let header = Header {
+            error_envelope_path: Some("error".to_string()),
+            ..Header::default()
+        };
+        let conditions = vec![
+            Condition {
+                is_or: false,
+                is_and: false,
+                is_not: false,
+                key: ConditionKey::ErrorCode,
+                operator: Operator::Equals,
+                case_sensitive: false,
+                value: "5".to_string(),
+                name: None,
+                environments: Vec::new(),
+            },
+            Condition {
+                is_or: false,
+                is_and: false,
+                is_not: false,
+                key: ConditionKey::ErrorMessage,
+                operator: Operator::Contains,
+                case_sensitive: false,
+                value: "not found".to_string(),
+                name: None,
+                environments: Vec::new(),
+            },
+        ];
+
+        let failed = evaluate_conditions_with_header(&conditions, &response, "test-file.greq", &header).unwrap();
+        assert!(failed.is_empty(), "Unexpected failures: {:?}", failed);
+    }
+
+    #[test]
+    fn test_error_envelope_keys_unavailable_without_envelope() {
+        let response = create_error_envelope_response();
+        let condition = Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::ErrorCode,
+            operator: Operator::Equals,
+            case_sensitive: false,
+            value: "5".to_string(),
+            name: None,
+            environments: Vec::new(),
+        };
+
+        let result = evaluate_single_condition_test(&condition, &response);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("requires the 'error-envelope-path' header property"));
+    }
+
+    fn create_cookie_response() -> Response {
+        Response {
+            status_code: 200,
+            all_headers: vec![
+                ("set-cookie".to_string(), "session=0123456789abcdef0123456789abcdef; Path=/; Secure; HttpOnly".to_string()),
+                ("set-cookie".to_string(), "theme=dark; Path=/settings; SameSite=Lax".to_string()),
+            ],
+            ..Response::default()
+        }
+    }
+
+    fn cookie_condition(name: &str, operator: Operator, value: &str) -> Condition {
+        Condition {
+            is_or: false,
+            is_and: false,
+            is_not: false,
+            key: ConditionKey::Cookie(name.to_string()),
+            operator,
+            case_sensitive: false,
+            value: value.to_string(),
+            name: None,
+            environments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cookie_value_from_multiple_set_cookie_headers() {
+        let response = create_cookie_response();
+
+        let session = cookie_condition("session", Operator::MatchesRegex, "^[a-f0-9]{32}$");
+        assert!(evaluate_single_condition_test(&session, &response).unwrap());
+
+        let theme = cookie_condition("theme", Operator::Equals, "dark");
+        assert!(evaluate_single_condition_test(&theme, &response).unwrap());
+    }
+
+    #[test]
+    fn test_cookie_attributes() {
+        let response = create_cookie_response();
+
+        let secure = cookie_condition("session.secure", Operator::Equals, "true");
+        assert!(evaluate_single_condition_test(&secure, &response).unwrap());
+
+        let theme_secure = cookie_condition("theme.secure", Operator::Equals, "false");
+        assert!(evaluate_single_condition_test(&theme_secure, &response).unwrap());
+
+        let same_site = cookie_condition("theme.samesite", Operator::Equals, "lax");
+        assert!(evaluate_single_condition_test(&same_site, &response).unwrap());
+    }
+
+    #[test]
+    fn test_cookie_exists() {
+        let response = create_cookie_response();
+
+        let existing = cookie_condition("session", Operator::Exists, "true");
+        assert!(evaluate_single_condition_test(&existing, &response).unwrap());
+
+        let missing = cookie_condition("tracking", Operator::Exists, "false");
+        assert!(evaluate_single_condition_test(&missing, &response).unwrap());
+    }
 }
@@ -0,0 +1,85 @@
+use crate::har::{DEFAULT_REDACTED_HEADERS, REDACTED_VALUE};
+use crate::models::GreqFile;
+use reqwest::Url;
+
+/// A copy-pasteable `curl` command for a resolved request, for `--show-curl`. Sensitive headers
+/// (the HAR defaults plus `extra_redacted_headers`) are replaced so the command can be shared
+pub fn curl_command(greq_file: &GreqFile, url: &str, extra_redacted_headers: &[String]) -> String {
+    let content = &greq_file.content;
+    let method = &content.request_line.method;
+    let mut parts = vec![match method.as_str() {
+        "HEAD" => format!("curl --head {}", shell_quote(url)),
+        _ => format!("curl -X {} {}", method, shell_quote(url)),
+    }];
+
+    let is_redacted = |name: &str| {
+        DEFAULT_REDACTED_HEADERS.iter().any(|redacted| name.eq_ignore_ascii_case(redacted))
+            || extra_redacted_headers.iter().any(|redacted| name.eq_ignore_ascii_case(redacted))
+    };
+    let mut headers: Vec<(&String, &String)> = content.headers.iter()
+        // curl derives both from the URL and the body
+        .filter(|(name, _)| !name.eq_ignore_ascii_case("host") && !name.eq_ignore_ascii_case("content-length"))
+        .collect();
+    headers.sort();
+    for (name, value) in headers {
+        let value = if is_redacted(name) { REDACTED_VALUE } else { value };
+        parts.push(format!("-H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+
+    let port = Url::parse(url).ok().and_then(|url| url.port_or_known_default()).unwrap_or(443);
+    for (host, address) in &greq_file.header.resolve {
+        parts.push(format!("--resolve {}", shell_quote(&format!("{host}:{port}:{address}"))));
+    }
+    if let Some(body) = &content.body {
+        parts.push(format!("--data-raw {}", shell_quote(body)));
+    }
+
+    parts.join(" \\\n  ")
+}
+
+/// Quote for POSIX shells: single quotes, with embedded single quotes closed and escaped
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Content, Footer, Header, RequestLine};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_curl_command() {
+        let greq_file = GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: "POST".to_string(),
+                    uri: "/users".to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                headers: HashMap::from([
+                    ("host".to_string(), "api.example.com".to_string()),
+                    ("authorization".to_string(), "Bearer secret".to_string()),
+                    ("x-tenant".to_string(), "acme".to_string()),
+                    ("content-type".to_string(), "application/json".to_string()),
+                    ("content-length".to_string(), "17".to_string()),
+                ]),
+                body: Some(r#"{"name": "O'Hara"}"#.to_string()),
+            },
+            footer: Footer::default(),
+            file_path: "users.greq".to_string(),
+        };
+
+        assert_eq!(
+            curl_command(&greq_file, "https://api.example.com/users", &["X-Tenant".to_string()]),
+            [
+                "curl -X POST 'https://api.example.com/users'",
+                "-H 'authorization: [REDACTED]'",
+                "-H 'content-type: application/json'",
+                "-H 'x-tenant: [REDACTED]'",
+                r#"--data-raw '{"name": "O'\''Hara"}'"#,
+            ].join(" \\\n  ")
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::models::IpVersion;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// Resolver for `dns-timeout` and `ip-version`: system lookups bounded by the timeout, keeping only
+/// addresses of the preferred family so a broken IPv6 route is never tried. `resolve:` overrides bypass it
+#[derive(Debug, Clone, Copy)]
+pub struct FilteringResolver {
+    ip_version: IpVersion,
+    timeout: Option<Duration>,
+}
+
+impl FilteringResolver {
+    pub fn new(ip_version: IpVersion, timeout: Option<Duration>) -> Self {
+        Self { ip_version, timeout }
+    }
+}
+
+impl Resolve for FilteringResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let Self { ip_version, timeout } = *self;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let lookup = tokio::net::lookup_host((host.as_str(), 0));
+            let addresses: Vec<SocketAddr> = match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, lookup)
+                    .await
+                    .map_err(|_| format!("DNS lookup of {host} timed out after {}ms (dns-timeout)", timeout.as_millis()))??
+                    .collect(),
+                None => lookup.await?.collect(),
+            };
+
+            let addresses = filter_addresses(addresses, ip_version);
+            if addresses.is_empty() {
+                return Err(format!("{host} has no {} address (ip-version)", ip_version.family()).into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The addresses of the preferred family, in resolver order
+fn filter_addresses(addresses: Vec<SocketAddr>, ip_version: IpVersion) -> Vec<SocketAddr> {
+    addresses
+        .into_iter()
+        .filter(|address| match ip_version {
+            IpVersion::Auto => true,
+            IpVersion::V4 => address.is_ipv4(),
+            IpVersion::V6 => address.is_ipv6(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_filter_addresses() {
+        let addresses: Vec<SocketAddr> = vec!["[::1]:0".parse().unwrap(), "127.0.0.1:0".parse().unwrap()];
+        assert_eq!(filter_addresses(addresses.clone(), IpVersion::V4), ["127.0.0.1:0".parse::<SocketAddr>().unwrap()]);
+        assert_eq!(filter_addresses(addresses.clone(), IpVersion::V6), ["[::1]:0".parse::<SocketAddr>().unwrap()]);
+        assert_eq!(filter_addresses(addresses.clone(), IpVersion::Auto), addresses);
+    }
+
+    #[tokio::test]
+    async fn test_v4_resolver_returns_no_ipv6_addresses() {
+        let resolver = FilteringResolver::new(IpVersion::V4, Some(Duration::from_secs(5)));
+        let addresses: Vec<SocketAddr> = resolver.resolve(Name::from_str("localhost").unwrap()).await.unwrap().collect();
+        assert!(!addresses.is_empty());
+        assert!(addresses.iter().all(SocketAddr::is_ipv4), "{addresses:?}");
+    }
+}
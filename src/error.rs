@@ -1,3 +1,4 @@
+use std::ops::Range;
 use thiserror::Error;
 
 /// Represents all possible errors that can occur in Greq
@@ -15,8 +16,9 @@ pub enum GreqError {
     #[error("Regex error: {0}")]
     Regex(#[from] regex::Error),
     
+    /// Carries the place in the .greq file when the parser knows it
     #[error("Parse error: {0}")]
-    Parse(String),
+    Parse(String, Option<ParseLocation>),
     
     #[error("Validation error: {0}")]
     Validation(String),
@@ -33,9 +35,36 @@ pub enum GreqError {
     #[error("File not found: {0}")]
     FileNotFound(String),
     
+    #[error("Response body exceeds the maximum size of {0} bytes (see --max-response-size)")]
+    ResponseTooLarge(usize),
+    
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+    
     #[error("Timeout error: request timed out")]
     Timeout,
 }
 
+/// Where a parse error was found in a .greq file, for tools that underline the offending text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLocation {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters, where the span starts
+    pub column: usize,
+    /// Byte range of the offending text within the file
+    pub span: Range<usize>,
+}
+
+impl GreqError {
+    /// Location of a parse error, when the parser could tell where it is
+    pub fn parse_location(&self) -> Option<&ParseLocation> {
+        match self {
+            GreqError::Parse(_, location) => location.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 /// Type alias for Result with GreqError
 pub type Result<T> = std::result::Result<T, GreqError>;
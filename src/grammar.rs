@@ -0,0 +1,200 @@
+use crate::models::Operator;
+use colored::*;
+
+/// A named element of the `.greq` grammar with an example and a one-line description
+pub struct GrammarEntry {
+    pub name: &'static str,
+    pub example: &'static str,
+    pub description: &'static str,
+}
+
+/// A condition operator as written in `.greq` files
+pub struct OperatorEntry {
+    pub name: &'static str,
+    pub operator: Operator,
+    pub description: &'static str,
+}
+
+/// HTTP methods accepted in the request line
+pub const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Server certificate properties available as `tls.<property>` condition keys
+pub const TLS_PROPERTIES: &[&str] = &["days-until-expiry", "issuer", "subject", "version"];
+
+/// Request phases available as `time.<phase>` condition keys
+pub const TIME_PHASES: &[&str] = &["connect", "ttfb", "download"];
+
+/// Header section properties
+pub const HEADER_PROPERTIES: &[GrammarEntry] = &[
+    GrammarEntry { name: "project", example: "project: User API Tests", description: "Test project name" },
+    GrammarEntry { name: "is-http", example: "is-http: true", description: "Use HTTP instead of HTTPS" },
+    GrammarEntry { name: "delimiter", example: "delimiter: $", description: "Section separator character" },
+    GrammarEntry { name: "extends", example: "extends: base.greq", description: "Base file to inherit from" },
+    GrammarEntry { name: "includes-file", example: "includes-file: partials.json", description: "JSON file of snippets inserted with {{> name}}" },
+    GrammarEntry { name: "body-merge", example: "body-merge: json", description: "Deep-merge the JSON body into the extended file's body" },
+    GrammarEntry { name: "depends-on", example: "depends-on: auth.greq", description: "File to execute first" },
+    GrammarEntry { name: "allow-dependency-failure", example: "allow-dependency-failure: true", description: "Continue if the dependency fails" },
+    GrammarEntry { name: "show-warnings", example: "show-warnings: false", description: "Show warning messages during execution" },
+    GrammarEntry { name: "timeout", example: "timeout: 5000", description: "Request timeout in milliseconds" },
+    GrammarEntry { name: "delay-before", example: "delay-before: 500", description: "Milliseconds to wait before sending the request" },
+    GrammarEntry { name: "delay-after", example: "delay-after: 500", description: "Milliseconds to wait after the response, before the next file" },
+    GrammarEntry { name: "number-of-retries", example: "number-of-retries: 3", description: "Retry attempts on failure" },
+    GrammarEntry { name: "retry-on-status", example: "retry-on-status: 429, 503", description: "Status codes to retry, honoring Retry-After" },
+    GrammarEntry { name: "execute-before", example: "execute-before: ./setup.sh", description: "Shell command to run before the request" },
+    GrammarEntry { name: "pre-request", example: "pre-request: ./sign.sh", description: "Command that gets the request as JSON and prints header/body overrides" },
+    GrammarEntry { name: "execute-after", example: "execute-after: ./cleanup.sh", description: "Shell command to run after the response" },
+    GrammarEntry { name: "set-environment.<name>", example: "set-environment.TOKEN: $(dependency.response-body.token)", description: "Set an environment variable for subsequent requests" },
+    GrammarEntry { name: "strict", example: "strict: true", description: "Fail instead of warning on suspicious requests" },
+    GrammarEntry { name: "ignore-base-url", example: "ignore-base-url: true", description: "Keep this file's scheme and host when --base-url is given" },
+    GrammarEntry { name: "expect-failure", example: "expect-failure: true", description: "Negative test; an error status is not flagged in the output" },
+    GrammarEntry { name: "protocol", example: "protocol: websocket", description: "Send the request over HTTP or as a WebSocket upgrade" },
+    GrammarEntry { name: "websocket-frames", example: "websocket-frames: 2", description: "Number of WebSocket frames to read" },
+    GrammarEntry { name: "validate-body", example: "validate-body: json", description: "Check the request body parses as json or xml before sending" },
+    GrammarEntry { name: "patch-type", example: "patch-type: json-patch", description: "Content type and body check for PATCH: merge or json-patch" },
+    GrammarEntry { name: "compress-body", example: "compress-body: gzip", description: "Compress the request body with gzip or deflate" },
+    GrammarEntry { name: "form", example: "form: username=$(environment.USER)", description: "URL-encoded form field sent as the request body" },
+    GrammarEntry { name: "expect", example: "expect: status-code equals 200", description: "Inline condition, for files without a footer" },
+    GrammarEntry { name: "client-cert", example: "client-cert: certs/client.pem", description: "PEM client certificate for mutual TLS" },
+    GrammarEntry { name: "client-key", example: "client-key: certs/client.key", description: "PKCS#8 PEM key matching client-cert" },
+    GrammarEntry { name: "client-identity", example: "client-identity: certs/client.p12", description: "PKCS#12 client identity for mutual TLS" },
+    GrammarEntry { name: "client-identity-password", example: "client-identity-password: secret", description: "Password of the client-identity bundle" },
+    GrammarEntry { name: "resolve", example: "resolve: api.example.com=10.0.0.7", description: "Connect to this IP for the host, keeping the Host header and SNI (repeatable)" },
+    GrammarEntry { name: "local-address", example: "local-address: 10.0.0.5", description: "Local IP address to send the request from" },
+    GrammarEntry { name: "dns-timeout", example: "dns-timeout: 2000", description: "Milliseconds a DNS lookup may take" },
+    GrammarEntry { name: "ip-version", example: "ip-version: v4", description: "Connect only over v4 or v6 addresses; auto by default" },
+    GrammarEntry { name: "baseline-ignore", example: "baseline-ignore: body.updated_at, headers.x-trace-id", description: "Snapshot paths that --baseline does not compare" },
+    GrammarEntry { name: "cache-header", example: "cache-header: x-edge-result", description: "Response header read by cache-status" },
+    GrammarEntry { name: "error-envelope-path", example: "error-envelope-path: error", description: "JSON path of the error object for error-code/error-message" },
+];
+
+/// Footer condition keys
+pub const CONDITION_KEYS: &[GrammarEntry] = &[
+    GrammarEntry { name: "status-code", example: "status-code", description: "HTTP status code" },
+    GrammarEntry { name: "latency", example: "latency", description: "Response time in milliseconds" },
+    GrammarEntry { name: "headers", example: "headers", description: "All response headers" },
+    GrammarEntry { name: "headers.<name>", example: "headers.content-type", description: "A specific response header" },
+    GrammarEntry { name: "trailers.<name>", example: "trailers.grpc-status", description: "A trailing header sent after the body (HTTP/2 or chunked HTTP/1.1)" },
+    GrammarEntry { name: "header-count", example: "header-count", description: "Number of distinct response headers" },
+    GrammarEntry { name: "header-count.<name>", example: "header-count.set-cookie", description: "How often a header occurred, counting repeated headers" },
+    GrammarEntry { name: "response-body", example: "response-body", description: "Response body content" },
+    GrammarEntry { name: "response-body.<path>", example: "response-body.users[0].id", description: "JSON path in the response body" },
+    GrammarEntry { name: "response-body.<path> | <fn>", example: "response-body.users | length", description: "JSON path piped through length, keys, values, first or last" },
+    GrammarEntry { name: "response-body.<path> any|all .<field>", example: "response-body.items any .status", description: "Require any or all array elements to match" },
+    GrammarEntry { name: "response-body.<path>[*].<field>", example: "response-body.items[*].id", description: "With exists, a field present in every (or no) array element" },
+    GrammarEntry { name: "cookie.<name>[.<attribute>]", example: "cookie.session.secure", description: "Cookie value or attribute from Set-Cookie" },
+    GrammarEntry { name: "location.query.<param>", example: "location.query.state", description: "URL-decoded query parameter of the Location header" },
+    GrammarEntry { name: "cache-status", example: "cache-status", description: "hit or miss from common CDN/proxy cache headers" },
+    GrammarEntry { name: "compressed", example: "compressed", description: "true when the body was sent with a Content-Encoding such as gzip or br" },
+    GrammarEntry { name: "from-cache", example: "from-cache", description: "true when the response came from a local cache; greq always fetches, so false" },
+    GrammarEntry { name: "redirect-count", example: "redirect-count", description: "Number of redirects followed before the final response (at most 10)" },
+    GrammarEntry { name: "final-url", example: "final-url", description: "URL of the final response after following redirects" },
+    GrammarEntry { name: "attempts", example: "attempts", description: "Requests sent until the response was accepted; 1 means no retry was needed" },
+    GrammarEntry { name: "scheme", example: "scheme", description: "Scheme (http or https) of the final URL, to catch downgrades on redirect" },
+    GrammarEntry { name: "tls.<property>", example: "tls.days-until-expiry", description: "Server certificate days-until-expiry, issuer or subject, or the negotiated TLS version" },
+    GrammarEntry { name: "time.<phase>", example: "time.ttfb", description: "Milliseconds spent in connect, ttfb (until the headers) or download" },
+    GrammarEntry { name: "charset", example: "charset", description: "Charset from the Content-Type header (utf-8 when absent)" },
+    GrammarEntry { name: "content-language", example: "content-language", description: "Content-Language header, e.g. after sending Accept-Language" },
+    GrammarEntry { name: "vary", example: "vary", description: "Vary header; repeated headers are joined with ', '" },
+    GrammarEntry { name: "frame[<n>]", example: "frame[0]", description: "The n-th received WebSocket frame" },
+    GrammarEntry { name: "part[<n>].body", example: "part[0].body", description: "Body of the n-th part of a multipart response" },
+    GrammarEntry { name: "part[<n>].headers.<name>", example: "part[0].headers.content-type", description: "A header of the n-th part of a multipart response" },
+    GrammarEntry { name: "error-code", example: "error-code", description: "code field of the error envelope" },
+    GrammarEntry { name: "error-message", example: "error-message", description: "message field of the error envelope" },
+];
+
+/// Optional prefixes of a condition line
+pub const CONDITION_PREFIXES: &[GrammarEntry] = &[
+    GrammarEntry { name: "or", example: "or status-code equals: 201", description: "Pass if this or the previous condition passes" },
+    GrammarEntry { name: "and", example: "and headers.content-type contains: json", description: "Group with the previous condition; failures name the group" },
+    GrammarEntry { name: "not", example: "not response-body contains: error", description: "Negate the condition" },
+    GrammarEntry { name: "[<name>]", example: "[auth] status-code equals: 401", description: "Name the condition so --only can select it" },
+    GrammarEntry { name: "[env:<name>,...]", example: "[env:prod] headers.x-ratelimit-limit exists: true", description: "Only check the condition when --env names one of the environments" },
+    GrammarEntry { name: "case-sensitive", example: "case-sensitive response-body contains: OK", description: "Compare strings case-sensitively" },
+];
+
+/// Footer lines that are not conditions
+pub const FOOTER_DIRECTIVES: &[GrammarEntry] = &[
+    GrammarEntry { name: "print", example: "print: response-body.user.id", description: "Log the value of a key without checking it" },
+    GrammarEntry { name: "include-conditions", example: "include-conditions: common.greq-footer", description: "Merge condition lines from a shared file; this file's conditions win by key and operator" },
+    GrammarEntry { name: "extract", example: "extract token: response-body.token", description: "Store a value as $(vars.<name>) for the files listed after this one" },
+    GrammarEntry { name: "transform", example: "transform response-body.data: base64-decode", description: "Decode a body value (base64-decode, url-decode, json-parse) before the conditions" },
+];
+
+/// Condition operators, used by the parser to resolve operator names
+pub const OPERATORS: &[OperatorEntry] = &[
+    OperatorEntry { name: "equals", operator: Operator::Equals, description: "Exact match" },
+    OperatorEntry { name: "contains", operator: Operator::Contains, description: "Substring match" },
+    OperatorEntry { name: "contains-any", operator: Operator::ContainsAny, description: "Contains any of a comma-separated list; not-contains-any guards against leaks" },
+    OperatorEntry { name: "matches-regex", operator: Operator::MatchesRegex, description: "Regular expression match" },
+    OperatorEntry { name: "less-than", operator: Operator::LessThan, description: "Numeric less than" },
+    OperatorEntry { name: "less-than-or-equal", operator: Operator::LessThanOrEqual, description: "Numeric less than or equal" },
+    OperatorEntry { name: "greater-than", operator: Operator::GreaterThan, description: "Numeric greater than" },
+    OperatorEntry { name: "greater-than-or-equal", operator: Operator::GreaterThanOrEqual, description: "Numeric greater than or equal" },
+    OperatorEntry { name: "starts-with", operator: Operator::StartsWith, description: "String prefix match" },
+    OperatorEntry { name: "ends-with", operator: Operator::EndsWith, description: "String suffix match" },
+    OperatorEntry { name: "within", operator: Operator::Within, description: "Numeric value within a tolerance, e.g. 500±50 or 500+/-50" },
+    OperatorEntry { name: "approx-equals", operator: Operator::ApproxEquals, description: "Numeric equality within a tolerance, e.g. 19.99±0.01 (default 1e-9)" },
+    OperatorEntry { name: "json-equals", operator: Operator::JsonEquals, description: "JSON value equality, ignoring key order and whitespace" },
+    OperatorEntry { name: "json-contains", operator: Operator::JsonContains, description: "JSON containing the expected keys and values; extra fields are ignored" },
+    OperatorEntry { name: "equals-file", operator: Operator::EqualsFile, description: "Equal to a fixture file, relative to the .greq file" },
+    OperatorEntry { name: "json-equals-file", operator: Operator::JsonEqualsFile, description: "JSON equality with a fixture file, ignoring key order" },
+    OperatorEntry { name: "equals-binary-file", operator: Operator::EqualsBinaryFile, description: "Byte-for-byte equal to a fixture file (raw body for response-body)" },
+    OperatorEntry { name: "type-equals", operator: Operator::TypeEquals, description: "JSON type of a response-body path: string, number, boolean, array, object or null" },
+    OperatorEntry { name: "sorted-ascending", operator: Operator::SortedAscending, description: "JSON array in ascending order (true/false)" },
+    OperatorEntry { name: "sorted-descending", operator: Operator::SortedDescending, description: "JSON array in descending order (true/false)" },
+    OperatorEntry { name: "equals-sequence", operator: Operator::EqualsSequence, description: "JSON array elements equal a comma-separated list, in order" },
+    OperatorEntry { name: "exists", operator: Operator::Exists, description: "Check that the value is present (true/false)" },
+];
+
+/// Check whether the HTTP method can be sent by greq
+pub fn method_is_valid(method: &str) -> bool {
+    HTTP_METHODS.contains(&method.to_uppercase().as_str())
+}
+
+/// Print the `.greq` grammar for `--help-grammar`
+pub fn print_grammar() {
+    println!("{}", "File layout".bold().blue());
+    println!("  header\n  ====\n  METHOD /path HTTP/1.1\n  headers\n\n  body\n  ====\n  [[env:name]] [[name]] [or|and] [not] [case-sensitive] key operator: value");
+
+    println!("\n{}", "HTTP methods".bold().blue());
+    println!("  {}", HTTP_METHODS.join(", "));
+
+    print_entries("Header properties", HEADER_PROPERTIES);
+    print_entries("Condition keys", CONDITION_KEYS);
+    print_entries("Condition prefixes", CONDITION_PREFIXES);
+    print_entries("Footer directives", FOOTER_DIRECTIVES);
+
+    println!("\n{}", "Operators".bold().blue());
+    let width = OPERATORS.iter().map(|entry| entry.name.len()).max().unwrap_or(0);
+    for entry in OPERATORS {
+        println!("  {}  {}", format!("{:<width$}", entry.name).green(), entry.description);
+    }
+}
+
+fn print_entries(title: &str, entries: &[GrammarEntry]) {
+    println!("\n{}", title.bold().blue());
+    let width = entries.iter().map(|entry| entry.name.len()).max().unwrap_or(0);
+    for entry in entries {
+        println!("  {}  {} {}", format!("{:<width$}", entry.name).green(), entry.description, format!("(e.g. {})", entry.example).dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_condition_key;
+
+    #[test]
+    fn test_condition_key_examples_parse() {
+        for entry in CONDITION_KEYS {
+            assert!(parse_condition_key(entry.example).is_ok(), "Example for {} does not parse", entry.name);
+        }
+    }
+
+    #[test]
+    fn test_method_is_valid() {
+        assert!(method_is_valid("GET"));
+        assert!(method_is_valid("options"));
+        assert!(!method_is_valid("FETCH"));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::error::Result;
+use crate::models::{GreqFile, Response};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Headers whose values are never written to a HAR file
+pub const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Replaces the value of a redacted header
+pub const REDACTED_VALUE: &str = "[REDACTED]";
+
+/// Collects executed requests and their responses as HAR 1.2 entries.
+/// Clones share the same entries so one recorder can be handed to every parallel execution
+#[derive(Debug, Clone, Default)]
+pub struct HarRecorder {
+    entries: Arc<Mutex<Vec<Value>>>,
+    redacted_headers: Vec<String>,
+}
+
+impl HarRecorder {
+    /// Create a recorder that redacts the default sensitive headers plus `extra_redacted_headers`
+    pub fn new(extra_redacted_headers: &[String]) -> Self {
+        let redacted_headers = DEFAULT_REDACTED_HEADERS
+            .iter()
+            .map(|name| name.to_string())
+            .chain(extra_redacted_headers.iter().map(|name| name.to_lowercase()))
+            .collect();
+
+        Self {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            redacted_headers,
+        }
+    }
+
+    /// Record a request that received a response
+    pub fn record(&self, greq_file: &GreqFile, response: &Response) {
+        let content = &greq_file.content;
+        let url = greq_file.request_url();
+        let latency_ms = response.latency.as_secs_f64() * 1000.0;
+        let started = Utc::now() - ChronoDuration::from_std(response.latency).unwrap_or_default();
+
+        let mut request_headers: Vec<(String, String)> = content
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        request_headers.sort();
+        let request_body = content.body.clone().unwrap_or_default();
+        let response_content_type = response.headers.get("content-type").cloned().unwrap_or_default();
+
+        let mut request = json!({
+            "method": content.request_line.method,
+            "url": url,
+            "httpVersion": content.request_line.version,
+            "cookies": [],
+            "headers": self.har_headers(&request_headers),
+            "queryString": [],
+            "headersSize": -1,
+            "bodySize": request_body.len(),
+        });
+        if content.body.is_some() {
+            request["postData"] = json!({
+                "mimeType": content.headers.get("content-type").cloned().unwrap_or_default(),
+                "text": request_body,
+            });
+        }
+
+        let entry = json!({
+            "startedDateTime": started.to_rfc3339(),
+            "time": latency_ms,
+            "request": request,
+            "response": {
+                "status": response.status_code,
+                "statusText": "",
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": self.har_headers(&response.all_headers),
+                "content": {
+                    "size": response.body.len(),
+                    "mimeType": response_content_type,
+                    "text": response.body,
+                },
+                "redirectURL": response.headers.get("location").cloned().unwrap_or_default(),
+                "headersSize": -1,
+                "bodySize": response.body.len(),
+            },
+            "cache": {},
+            "timings": {
+                "send": 0,
+                "wait": latency_ms,
+                "receive": 0,
+            },
+        });
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(entry);
+        }
+    }
+
+    /// Build the complete HAR document
+    pub fn to_har(&self) -> Value {
+        let entries = self.entries.lock().map(|entries| entries.clone()).unwrap_or_default();
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": "greq",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "entries": entries,
+            }
+        })
+    }
+
+    /// Write the HAR document to a file
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let har = serde_json::to_string_pretty(&self.to_har())?;
+        fs::write(path, har)?;
+        Ok(())
+    }
+
+    fn har_headers(&self, headers: &[(String, String)]) -> Value {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.redacted_headers.contains(&name.to_lowercase()) {
+                    REDACTED_VALUE
+                } else {
+                    value.as_str()
+                };
+                json!({ "name": name, "value": value })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Content, Footer, Header, RequestLine};
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn create_greq_file() -> GreqFile {
+        let mut headers = HashMap::new();
+        headers.insert("host".to_string(), "api.example.com".to_string());
+        headers.insert("authorization".to_string(), "Bearer secret".to_string());
+        headers.insert("x-tenant".to_string(), "acme".to_string());
+
+        GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: "POST".to_string(),
+                    uri: "/users".to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                headers,
+                body: Some("{\"name\": \"test\"}".to_string()),
+            },
+            footer: Footer::default(),
+            file_path: "create-user.greq".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_har_entry_with_redacted_headers() {
+        let recorder = HarRecorder::new(&["X-Tenant".to_string()]);
+        let response = Response {
+            status_code: 201,
+            all_headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("set-cookie".to_string(), "session=abc".to_string()),
+            ],
+            body: "{\"id\": 1}".to_string(),
+            latency: Duration::from_millis(42),
+            ..Response::default()
+        };
+
+        recorder.record(&create_greq_file(), &response);
+        let har = recorder.to_har();
+
+        assert_eq!(har["log"]["version"], "1.2");
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(entry["request"]["method"], "POST");
+        assert_eq!(entry["request"]["url"], "https://api.example.com/users");
+        assert_eq!(entry["request"]["postData"]["text"], "{\"name\": \"test\"}");
+        assert_eq!(entry["response"]["status"], 201);
+
+        let headers = entry["request"]["headers"].as_array().unwrap();
+        let header_value = |name: &str| headers.iter().find(|h| h["name"] == name).unwrap()["value"].clone();
+        assert_eq!(header_value("authorization"), REDACTED_VALUE);
+        assert_eq!(header_value("x-tenant"), REDACTED_VALUE);
+        assert_eq!(header_value("host"), "api.example.com");
+        assert_eq!(entry["response"]["headers"][1]["value"], REDACTED_VALUE);
+    }
+
+    #[test]
+    fn test_clones_share_entries_and_write_file() {
+        let recorder = HarRecorder::new(&[]);
+        let clone = recorder.clone();
+        clone.record(&create_greq_file(), &Response::default());
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("run.har");
+        recorder.write_to(&path).unwrap();
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["log"]["entries"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,280 @@
+use serde_json::Value;
+use std::fmt;
+
+/// A segment of a JSON path such as `users[0].name`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathPart {
+    Property(String),
+    Index(usize),
+    /// `[*]`: every element of an array, only followed by `exists` sweeps
+    Wildcard,
+}
+
+/// Why a JSON path could not be followed; callers add the file and location context
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPathError {
+    InvalidIndex(String),
+    PropertyNotFound { key: String, path: String },
+    NotAnObject { key: String, path: String },
+    IndexOutOfBounds { index: usize, length: usize, path: String },
+    NotAnArray { index: usize, path: String },
+    UnexpectedWildcard { path: String },
+    WildcardNotAnArray { location: String, found: &'static str },
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonPathError::InvalidIndex(index) => write!(f, "Invalid array index: {index}"),
+            JsonPathError::PropertyNotFound { key, path } => {
+                write!(f, "Property '{key}' not found in JSON path '{path}'")
+            }
+            JsonPathError::NotAnObject { key, path } => {
+                write!(f, "Cannot access property '{key}' on non-object in JSON path '{path}'")
+            }
+            JsonPathError::IndexOutOfBounds { index, length, path } => {
+                write!(f, "Array index {index} out of bounds (length: {length}) in JSON path '{path}'")
+            }
+            JsonPathError::NotAnArray { index, path } => {
+                write!(f, "Cannot access index {index} on non-array in JSON path '{path}'")
+            }
+            JsonPathError::UnexpectedWildcard { path } => {
+                write!(f, "Wildcard '[*]' in JSON path '{path}' is only supported with the exists operator")
+            }
+            JsonPathError::WildcardNotAnArray { location, found } => {
+                write!(f, "Wildcard '[*]' expects an array at '{location}', found {found}")
+            }
+        }
+    }
+}
+
+/// Parse a JSON path string into path parts; an empty path addresses the whole value
+pub fn parse_json_path(path: &str) -> Result<Vec<PathPart>, JsonPathError> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = path.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '.' => {
+                if !current.is_empty() {
+                    parts.push(PathPart::Property(current.clone()));
+                    current.clear();
+                }
+            }
+            '[' => {
+                if !current.is_empty() {
+                    parts.push(PathPart::Property(current.clone()));
+                    current.clear();
+                }
+
+                let index_str: String = chars.by_ref().take_while(|&ch| ch != ']').collect();
+                if index_str == "*" {
+                    parts.push(PathPart::Wildcard);
+                    continue;
+                }
+                let index = index_str
+                    .parse()
+                    .map_err(|_| JsonPathError::InvalidIndex(index_str.clone()))?;
+                parts.push(PathPart::Index(index));
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(PathPart::Property(current));
+    }
+
+    Ok(parts)
+}
+
+/// Follow a JSON path through a value
+pub fn navigate_json_path<'a>(value: &'a Value, path: &str) -> Result<&'a Value, JsonPathError> {
+    let mut current = value;
+
+    for part in parse_json_path(path)? {
+        current = match (part, current) {
+            (PathPart::Property(key), Value::Object(obj)) => obj.get(&key).ok_or_else(|| {
+                JsonPathError::PropertyNotFound { key, path: path.to_string() }
+            })?,
+            (PathPart::Property(key), _) => {
+                return Err(JsonPathError::NotAnObject { key, path: path.to_string() })
+            }
+            (PathPart::Index(index), Value::Array(arr)) => arr.get(index).ok_or_else(|| {
+                JsonPathError::IndexOutOfBounds { index, length: arr.len(), path: path.to_string() }
+            })?,
+            (PathPart::Index(index), _) => {
+                return Err(JsonPathError::NotAnArray { index, path: path.to_string() })
+            }
+            (PathPart::Wildcard, _) => return Err(JsonPathError::UnexpectedWildcard { path: path.to_string() }),
+        };
+    }
+
+    Ok(current)
+}
+
+/// Follow a JSON path through a value, returning the value for modification
+pub fn navigate_json_path_mut<'a>(value: &'a mut Value, path: &str) -> Result<&'a mut Value, JsonPathError> {
+    let mut current = value;
+
+    for part in parse_json_path(path)? {
+        current = match (part, current) {
+            (PathPart::Property(key), Value::Object(obj)) => match obj.get_mut(&key) {
+                Some(value) => value,
+                None => return Err(JsonPathError::PropertyNotFound { key, path: path.to_string() }),
+            },
+            (PathPart::Property(key), _) => {
+                return Err(JsonPathError::NotAnObject { key, path: path.to_string() })
+            }
+            (PathPart::Index(index), Value::Array(arr)) => {
+                let length = arr.len();
+                arr.get_mut(index).ok_or_else(|| {
+                    JsonPathError::IndexOutOfBounds { index, length, path: path.to_string() }
+                })?
+            }
+            (PathPart::Index(index), _) => {
+                return Err(JsonPathError::NotAnArray { index, path: path.to_string() })
+            }
+            (PathPart::Wildcard, _) => return Err(JsonPathError::UnexpectedWildcard { path: path.to_string() }),
+        };
+    }
+
+    Ok(current)
+}
+
+/// Expand the `[*]` wildcards of a path into the concrete path of every element, e.g.
+/// `items[*].id` into `items[0].id`, `items[1].id`, each with whether it exists.
+/// A missing array before a wildcard is reported as the missing path itself
+pub fn expand_wildcard_path(value: &Value, path: &str) -> Result<Vec<(String, bool)>, JsonPathError> {
+    let parts = parse_json_path(path)?;
+    let mut found = Vec::new();
+    expand_parts(Some(value), &parts, String::new(), &mut found)?;
+    Ok(found)
+}
+
+fn expand_parts(value: Option<&Value>, parts: &[PathPart], location: String, found: &mut Vec<(String, bool)>) -> Result<(), JsonPathError> {
+    let Some((part, rest)) = parts.split_first() else {
+        found.push((location, value.is_some()));
+        return Ok(());
+    };
+    let Some(value) = value else {
+        found.push((location, false));
+        return Ok(());
+    };
+
+    match part {
+        PathPart::Property(key) => {
+            let location = if location.is_empty() { key.clone() } else { format!("{location}.{key}") };
+            expand_parts(value.get(key), rest, location, found)
+        }
+        PathPart::Index(index) => expand_parts(value.get(index), rest, format!("{location}[{index}]"), found),
+        PathPart::Wildcard => {
+            let Value::Array(elements) = value else {
+                let location = if location.is_empty() { "response-body".to_string() } else { location };
+                return Err(JsonPathError::WildcardNotAnArray { location, found: json_type_name(value) });
+            };
+            for (index, element) in elements.iter().enumerate() {
+                expand_parts(Some(element), rest, format!("{location}[{index}]"), found)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The value at a JSON path, or `None` when the path does not exist
+pub fn get_path(value: &Value, path: &str) -> Option<Value> {
+    navigate_json_path(value, path).ok().cloned()
+}
+
+/// The type of a JSON value for error messages, with its article
+pub fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Convert a JSON value to the text used by conditions and placeholders; strings are unquoted
+pub fn json_value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Response;
+    use crate::placeholders::replace_placeholders;
+    use serde_json::json;
+
+    #[test]
+    fn test_navigate_json_path() {
+        let value = json!({"users": [{"name": "Ann", "tags": ["a", "b"]}], "count": 1});
+
+        assert_eq!(get_path(&value, "users[0].tags[1]"), Some(json!("b")));
+        assert_eq!(get_path(&value, ""), Some(value.clone()));
+        assert_eq!(get_path(&value, "users[3]"), None);
+        assert_eq!(
+            navigate_json_path(&value, "count.value").unwrap_err().to_string(),
+            "Cannot access property 'value' on non-object in JSON path 'count.value'"
+        );
+        assert_eq!(
+            navigate_json_path(&value, "users[x]").unwrap_err(),
+            JsonPathError::InvalidIndex("x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expand_wildcard_path() {
+        let value = json!({"orders": [{"lines": [{"sku": "a"}, {}]}, {"lines": []}, {}]});
+
+        assert_eq!(
+            expand_wildcard_path(&value, "orders[*].lines[*].sku").unwrap(),
+            [("orders[0].lines[0].sku".to_string(), true), ("orders[0].lines[1].sku".to_string(), false), ("orders[2].lines".to_string(), false)]
+        );
+        assert_eq!(parse_json_path("items[*].id").unwrap(), [PathPart::Property("items".to_string()), PathPart::Wildcard, PathPart::Property("id".to_string())]);
+        assert_eq!(
+            navigate_json_path(&value, "orders[*]").unwrap_err(),
+            JsonPathError::UnexpectedWildcard { path: "orders[*]".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_response_json_is_parsed_once() {
+        let response = Response {
+            body: r#"{"id": 7}"#.to_string(),
+            ..Response::default()
+        };
+
+        let first = response.json().unwrap();
+        let second = response.json().unwrap();
+
+        assert!(std::ptr::eq(first, second), "The parsed body should be cached");
+        assert_eq!(response.get_path("id"), Some(json!(7)));
+        assert!(Response { body: "not json".to_string(), ..Response::default() }.json().is_none());
+    }
+
+    #[test]
+    fn test_response_paths_match_placeholders() {
+        let response = Response {
+            body: r#"{"user": {"id": 12345678901234567890, "roles": ["admin"], "active": true, "manager": null}}"#.to_string(),
+            ..Response::default()
+        };
+
+        for path in ["user.id", "user.roles", "user.roles[0]", "user.active", "user.manager", "user"] {
+            let placeholder = replace_placeholders(&format!("$(dependency.response-body.{path})"), &response).unwrap();
+            let value = response.get_path(path).unwrap();
+            assert_eq!(json_value_to_string(&value), placeholder, "Mismatch for {path}");
+        }
+    }
+}
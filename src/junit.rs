@@ -0,0 +1,256 @@
+use crate::error::Result;
+use crate::models::ExecutionResult;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Suite name used for files without a `project` header
+const DEFAULT_SUITE_NAME: &str = "greq";
+
+/// How a test case `name` is derived from the `.greq` file path
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JunitTestName {
+    /// File name, e.g. `create-user.greq`
+    #[default]
+    File,
+    /// File name without the extension, e.g. `create-user`
+    Stem,
+    /// The path as given on the command line
+    Path,
+}
+
+impl JunitTestName {
+    /// Parse the `--junit-test-name` value
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        match value {
+            "file" => Ok(Self::File),
+            "stem" => Ok(Self::Stem),
+            "path" => Ok(Self::Path),
+            _ => Err(format!("expected file, stem or path, got '{value}'")),
+        }
+    }
+}
+
+/// Writes execution results as a JUnit XML report. Files are grouped into one
+/// `<testsuite>` per project, wrapped in a `<testsuites>` element
+#[derive(Debug, Clone, Default)]
+pub struct JunitReport {
+    /// Prepended to the project name to form the `classname` of each test case
+    pub classname_prefix: Option<String>,
+    pub test_name: JunitTestName,
+    /// When the run started, reported as the `timestamp` of the suites; left out when unknown
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl JunitReport {
+    /// Render the complete JUnit XML document
+    pub fn render(&self, results: &[ExecutionResult]) -> String {
+        let timestamp = self.started_at
+            .map(|started_at| format!(" timestamp=\"{}\"", started_at.to_rfc3339_opts(SecondsFormat::Secs, true)))
+            .unwrap_or_default();
+
+        // Suites keep the order in which their first file was executed
+        let mut suites: Vec<(String, Vec<usize>)> = Vec::new();
+        for (index, result) in results.iter().enumerate() {
+            let suite_name = result.project.clone().unwrap_or_else(|| DEFAULT_SUITE_NAME.to_string());
+            match suites.iter_mut().find(|(name, _)| *name == suite_name) {
+                Some((_, indexes)) => indexes.push(index),
+                None => suites.push((suite_name, vec![index])),
+            }
+        }
+
+        let total_failures = results.iter().filter(|r| !r.success && r.error.is_none()).count();
+        let total_errors = results.iter().filter(|r| !r.success && r.error.is_some()).count();
+        let total_time: f64 = results.iter().map(result_seconds).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"greq\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\"{}>",
+            results.len(), total_failures, total_errors, total_time, timestamp
+        );
+
+        for (suite_name, indexes) in &suites {
+            let suite_results: Vec<&ExecutionResult> = indexes.iter().map(|&i| &results[i]).collect();
+            let failures = suite_results.iter().filter(|r| !r.success && r.error.is_none()).count();
+            let errors = suite_results.iter().filter(|r| !r.success && r.error.is_some()).count();
+            let time: f64 = suite_results.iter().map(|r| result_seconds(r)).sum();
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\"{}>",
+                escape_xml(suite_name), suite_results.len(), failures, errors, time, timestamp
+            );
+            for &index in indexes {
+                self.render_test_case(&mut xml, &results[index], suite_name);
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    /// Write the JUnit XML report to a file
+    pub fn write_to<P: AsRef<Path>>(&self, results: &[ExecutionResult], path: P) -> Result<()> {
+        fs::write(path, self.render(results))?;
+        Ok(())
+    }
+
+    fn render_test_case(&self, xml: &mut String, result: &ExecutionResult, suite_name: &str) {
+        let classname = match &self.classname_prefix {
+            Some(prefix) => format!("{prefix}.{suite_name}"),
+            None => suite_name.to_string(),
+        };
+        let _ = writeln!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\">",
+            escape_xml(&classname), escape_xml(&self.test_case_name(&result.file_path)), result_seconds(result)
+        );
+
+        let mut properties = vec![("file", result.file_path.clone())];
+        if let Some(request_id) = &result.request_id {
+            properties.push(("request-id", request_id.clone()));
+        }
+        if let Some(request) = &result.request {
+            properties.push(("method", request.method.clone()));
+            properties.push(("url", request.url.clone()));
+        }
+        if let Some(response) = &result.response {
+            properties.push(("status-code", response.status_code.to_string()));
+            properties.push(("latency-ms", response.latency.as_millis().to_string()));
+        }
+        xml.push_str("      <properties>\n");
+        for (name, value) in properties {
+            let _ = writeln!(xml, "        <property name=\"{}\" value=\"{}\"/>", name, escape_xml(&value));
+        }
+        xml.push_str("      </properties>\n");
+
+        if let Some(error) = &result.error {
+            let _ = writeln!(xml, "      <error message=\"{}\">{}</error>", escape_xml(error), escape_xml(error));
+        } else if !result.success {
+            let message = result.failed_conditions.first().map(String::as_str).unwrap_or("Conditions failed");
+            let _ = writeln!(
+                xml,
+                "      <failure message=\"{}\">{}</failure>",
+                escape_xml(message), escape_xml(&result.failed_conditions.join("\n"))
+            );
+        }
+
+        if let Some(response) = result.response.as_ref().filter(|response| !response.body.is_empty()) {
+            let body = remove_invalid_xml_chars(&response.body).replace("]]>", "]]]]><![CDATA[>");
+            let _ = writeln!(xml, "      <system-out><![CDATA[{}]]></system-out>", body);
+        }
+
+        xml.push_str("    </testcase>\n");
+    }
+
+    fn test_case_name(&self, file_path: &str) -> String {
+        let path = Path::new(file_path);
+        let name = match self.test_name {
+            JunitTestName::Path => None,
+            JunitTestName::File => path.file_name(),
+            JunitTestName::Stem => path.file_stem(),
+        };
+        name.and_then(|name| name.to_str()).unwrap_or(file_path).to_string()
+    }
+}
+
+fn result_seconds(result: &ExecutionResult) -> f64 {
+    result.response.as_ref().map(|response| response.latency.as_secs_f64()).unwrap_or_default()
+}
+
+fn escape_xml(text: &str) -> String {
+    remove_invalid_xml_chars(text)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Drop the characters XML 1.0 does not allow even escaped, e.g. the control bytes of a binary body
+fn remove_invalid_xml_chars(text: &str) -> String {
+    text.chars()
+        .filter(|&c| matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Response, SentRequest};
+    use std::time::Duration;
+
+    fn create_result(file_path: &str, project: &str, success: bool, body: &str) -> ExecutionResult {
+        ExecutionResult {
+            project: Some(project.to_string()),
+            request: Some(SentRequest {
+                method: if success { "GET" } else { "POST" }.to_string(),
+                url: format!("https://api.example.com/{}", file_path.trim_end_matches(".greq")),
+            }),
+            response: Some(Response {
+                status_code: if success { 200 } else { 500 },
+                body: body.to_string(),
+                latency: Duration::from_millis(250),
+                ..Response::default()
+            }),
+            failed_conditions: if success { Vec::new() } else { vec!["status-code equals '200' (actual: '500')".to_string()] },
+            ..ExecutionResult::for_file(file_path, success)
+        }
+    }
+
+    #[test]
+    fn test_suites_are_grouped_by_project() {
+        let results = vec![
+            create_result("users.greq", "Users", true, "{\"users\": []}"),
+            create_result("orders.greq", "Orders", false, "<error>]]></error>"),
+            create_result("profile.greq", "Users", true, ""),
+        ];
+
+        let report = JunitReport {
+            classname_prefix: Some("api".to_string()),
+            test_name: JunitTestName::Stem,
+            started_at: DateTime::parse_from_rfc3339("2026-03-01T08:30:00Z").ok().map(|time| time.to_utc()),
+        };
+        let xml = report.render(&results);
+
+        assert!(xml.contains("<testsuites name=\"greq\" tests=\"3\" failures=\"1\" errors=\"0\" time=\"0.750\" timestamp=\"2026-03-01T08:30:00Z\">"));
+        assert!(xml.contains("<testsuite name=\"Users\" tests=\"2\" failures=\"0\""));
+        assert!(xml.contains("<testsuite name=\"Orders\" tests=\"1\" failures=\"1\""));
+        assert!(xml.contains("<testcase classname=\"api.Users\" name=\"users\" time=\"0.250\">"));
+        assert!(xml.contains("<property name=\"method\" value=\"POST\"/>"));
+        assert!(xml.contains("<property name=\"url\" value=\"https://api.example.com/orders\"/>"));
+        assert!(xml.contains("<failure message=\"status-code equals &apos;200&apos; (actual: &apos;500&apos;)\">"));
+        assert!(xml.contains("<system-out><![CDATA[<error>]]]]><![CDATA[></error>]]></system-out>"));
+        assert_eq!(xml.matches("<system-out>").count(), 2);
+    }
+
+    #[test]
+    fn test_errors_and_default_names() {
+        let result = ExecutionResult {
+            error: Some("HTTP error: connection refused".to_string()),
+            request_id: Some("4f9c2a7e-1b3d-4c8e-9a6f-2d5b7e0c1a93".to_string()),
+            ..ExecutionResult::for_file("missing/login.greq", false)
+        };
+
+        let xml = JunitReport::default().render(&[result]);
+
+        assert!(xml.contains("<testsuite name=\"greq\" tests=\"1\" failures=\"0\" errors=\"1\" time=\"0.000\">"));
+        assert!(xml.contains("<testcase classname=\"greq\" name=\"login.greq\" time=\"0.000\">"));
+        assert!(xml.contains("<error message=\"HTTP error: connection refused\">"));
+        assert!(xml.contains("<property name=\"file\" value=\"missing/login.greq\"/>"));
+        assert!(xml.contains("<property name=\"request-id\" value=\"4f9c2a7e-1b3d-4c8e-9a6f-2d5b7e0c1a93\"/>"));
+        // A file that never sent its request has no request properties
+        assert!(!xml.contains("<property name=\"method\""));
+    }
+
+    #[test]
+    fn test_binary_body_keeps_the_report_valid() {
+        let result = create_result("image.greq", "Files", true, "\u{89}PNG\r\n\u{1a}\n\u{0}\u{0}\u{0}\rIHDR\u{fffe}");
+
+        let xml = JunitReport::default().render(&[result]);
+
+        assert!(xml.contains("<system-out><![CDATA[\u{89}PNG\r\n\n\rIHDR]]></system-out>"), "{xml:?}");
+    }
+}
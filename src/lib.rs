@@ -6,5 +6,22 @@ pub mod conditions;
 pub mod placeholders;
 pub mod logger;
 pub mod error;
+pub mod grammar;
+pub mod json_path;
+pub mod har;
+pub mod curl;
+pub mod junit;
+pub mod mock;
+pub mod monitor;
+pub mod changed;
+pub mod rate_limit;
+pub mod baseline;
+pub mod artifacts;
+pub mod request_log;
+pub mod timing;
+pub mod redirect;
+pub mod dns;
+pub mod tls_version;
+pub mod profile;
 
 pub use error::{GreqError, Result};
@@ -1,15 +1,73 @@
+use std::cell::RefCell;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use dirs;
 use log::LevelFilter;
 use env_logger::Builder;
+use std::future::Future;
 use std::io::Write;
 
+tokio::task_local! {
+    /// The `.greq` file executed by the current task, reported in JSON log records
+    static CURRENT_FILE: RefCell<String>;
+}
+
+/// How log records are written to the console
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogFormat {
+    /// `[timestamp LEVEL] message` lines
+    #[default]
+    Human,
+    /// One JSON object per record, for log aggregation systems
+    Json,
+}
+
+impl LogFormat {
+    /// Parse the `--log-format` value
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        match value {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("expected human or json, got '{value}'")),
+        }
+    }
+}
+
+/// Run a future with `file` reported as the file being processed by its log records
+pub async fn with_current_file<F: Future>(file: String, future: F) -> F::Output {
+    CURRENT_FILE.scope(RefCell::new(file), future).await
+}
+
+/// Report `file` as the file being processed until the returned guard is dropped,
+/// for a single file executed inside a `with_current_file` scope
+pub fn enter_file(file: String) -> CurrentFileGuard {
+    let previous = CURRENT_FILE.try_with(|current| current.replace(file)).ok();
+    CurrentFileGuard { previous }
+}
+
+/// Restores the previously reported file when dropped
+pub struct CurrentFileGuard {
+    previous: Option<String>,
+}
+
+impl Drop for CurrentFileGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            let _ = CURRENT_FILE.try_with(|current| current.replace(previous));
+        }
+    }
+}
+
 /// Initialize logging for the application
 /// Detailed logs go to AppData/Local/greq/greq.log
 /// Console shows only important information with colors
 pub fn init_logger() -> crate::Result<()> {
+    init_logger_with_format(LogFormat::Human)
+}
+
+/// Initialize logging with the given console format
+pub fn init_logger_with_format(format: LogFormat) -> crate::Result<()> {
     let log_dir = get_log_directory()?;
     fs::create_dir_all(&log_dir)?;
     
@@ -24,9 +82,9 @@ pub fn init_logger() -> crate::Result<()> {
         .parse()
         .unwrap_or(if cfg!(debug_assertions) { LevelFilter::Info } else { LevelFilter::Warn });
     
-    builder
-        .filter_level(level)
-        .format(|buf, record| {
+    builder.filter_level(level).target(env_logger::Target::Stdout);
+    match format {
+        LogFormat::Human => builder.format(|buf, record| {
             // Simplified format for console - no file paths/line numbers
             writeln!(
                 buf,
@@ -35,8 +93,12 @@ pub fn init_logger() -> crate::Result<()> {
                 record.level(),
                 record.args()
             )
-        })
-        .target(env_logger::Target::Stdout);
+        }),
+        LogFormat::Json => builder.format(|buf, record| {
+            let file = CURRENT_FILE.try_with(|file| file.borrow().clone()).ok();
+            writeln!(buf, "{}", format_json_record(record, file.as_deref()))
+        }),
+    };
     
     // For file logging, we would need to implement a custom logger
     // For now, we'll use the default env_logger to stdout
@@ -46,6 +108,20 @@ pub fn init_logger() -> crate::Result<()> {
     Ok(())
 }
 
+/// A log record as a single-line JSON object; `file` is omitted outside of a file's execution
+fn format_json_record(record: &log::Record, file: Option<&str>) -> String {
+    let mut entry = serde_json::json!({
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+    });
+    if let Some(file) = file {
+        entry["file"] = serde_json::Value::String(file.to_string());
+    }
+    entry.to_string()
+}
+
 /// Get the appropriate log directory for the current OS
 fn get_log_directory() -> crate::Result<PathBuf> {
     #[cfg(target_os = "windows")]
@@ -66,3 +142,50 @@ fn get_log_directory() -> crate::Result<PathBuf> {
         Ok(home.join(".local/share/greq"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_json_record() {
+        let record = log::Record::builder()
+            .level(log::Level::Warn)
+            .target("greq::executor")
+            .args(format_args!("Retrying \"users.greq\""))
+            .build();
+
+        let entry: Value = serde_json::from_str(&format_json_record(&record, Some("tests/users.greq"))).unwrap();
+
+        assert_eq!(entry["level"], "WARN");
+        assert_eq!(entry["target"], "greq::executor");
+        assert_eq!(entry["message"], "Retrying \"users.greq\"");
+        assert_eq!(entry["file"], "tests/users.greq");
+        assert!(entry["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(format_json_record(&record, None).find("\"file\"").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_current_file_is_scoped_to_the_task() {
+        let file = with_current_file("a.greq".to_string(), async { CURRENT_FILE.with(|file| file.borrow().clone()) }).await;
+
+        assert_eq!(file, "a.greq");
+        assert!(CURRENT_FILE.try_with(|file| file.borrow().clone()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_entered_file_is_reported_until_the_guard_drops() {
+        let files = with_current_file("b.greq".to_string(), async {
+            let current = || CURRENT_FILE.with(|file| file.borrow().clone());
+            let inside = {
+                let _file = enter_file("a.greq".to_string());
+                current()
+            };
+            (inside, current())
+        }).await;
+
+        assert_eq!(files, ("a.greq".to_string(), "b.greq".to_string()));
+        drop(enter_file("c.greq".to_string()));
+    }
+}
@@ -1,26 +1,326 @@
-use greq::logger;
-use greq::executor::{execute_multiple_greq_files, print_execution_results, all_successful};
-use clap::Parser;
+use greq::artifacts::{load_run_results, select_failed_files, ArtifactWriter};
+use greq::baseline::Baseline;
+use greq::request_log::RequestLog;
+use greq::changed::{git_changed_files, select_changed_files};
+use greq::logger::{self, LogFormat};
+use greq::executor::{
+    all_successful, count_passed, execute_greq_file_with_options, parse_base_url, print_execution_result, print_execution_summary, print_execution_totals, print_results_by_project,
+    print_results_heading, stream_greq_files_with_options,
+};
+use greq::grammar::print_grammar;
+use greq::har::HarRecorder;
+use greq::junit::{JunitReport, JunitTestName};
+use greq::mock::MockResponses;
+use greq::rate_limit::RateLimiter;
+use greq::profile::{find_config_file, load_profile_args};
+use greq::monitor::{run_monitor, MonitorSettings, MonitorStop};
+use greq::models::{ExecutionOptions, ExecutionResult, SuiteVariables};
+use chrono::Utc;
+use clap::{CommandFactory, Parser};
+use futures_util::StreamExt;
 use colored::*;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::pin::pin;
 use std::process;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Greq - A robust web API tester with inheritance, dependencies and dynamic requests support
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+// Options given on the command line come after the ones from a profile and win
+#[command(args_override_self = true)]
 struct Cli {
     /// Greq files to execute
-    #[arg(required = true, help = "One or more .greq files to execute")]
+    #[arg(required_unless_present = "help_grammar", help = "One or more .greq files to execute")]
     files: Vec<PathBuf>,
     
     /// Enable verbose logging
     #[arg(short, long, help = "Enable verbose logging")]
     verbose: bool,
+    
+    /// Print only the totals and the failing files
+    #[arg(long, help = "Print only the totals and the failing files with their first failure")]
+    summary_only: bool,
+    
+    /// Group the results by project
+    #[arg(long, conflicts_with_all = ["summary_only", "monitor"], help = "Print the results once all files ran, grouped by project with per-project subtotals")]
+    group_by_project: bool,
+    
+    /// Compact progress for CI logs
+    #[arg(long, conflicts_with_all = ["summary_only", "verbose", "explain", "show_curl", "monitor"], help = "Print a . or F per completed file, then the results of all files in command-line order")]
+    progress: bool,
+    
+    /// Disable retries for every request
+    #[arg(long, help = "Make a single attempt per request, ignoring number-of-retries")]
+    no_retry: bool,
+    
+    /// Limit on response body size
+    #[arg(long, value_name = "BYTES", help = "Largest response body to read, in bytes (default: 50MB)")]
+    max_response_size: Option<usize>,
+    
+    /// Truncate instead of failing on oversized responses
+    #[arg(long, requires = "max_response_size", help = "Truncate response bodies at --max-response-size with a warning instead of failing")]
+    truncate_large_responses: bool,
+    
+    /// Evaluate only the named conditions
+    #[arg(long, visible_alias = "filter-conditions", value_name = "NAME", help = "Evaluate only conditions tagged [NAME] in the given files (can be repeated)")]
+    only: Vec<String>,
+    
+    /// Active environment for [env:...] condition guards
+    #[arg(long, value_name = "NAME", help = "Environment the suite runs against; conditions tagged [env:OTHER] are skipped")]
+    env: Option<String>,
+    
+    /// Header carrying the generated request id
+    #[arg(long, value_name = "NAME", default_value = "X-Request-Id", help = "Header used to send a generated request id, unless the file sets it")]
+    request_id_header: String,
+    
+    /// Do not send a generated request id
+    #[arg(long, help = "Do not add a generated request id header to requests")]
+    no_request_id: bool,
+    
+    /// Do not run commands from .greq files
+    #[arg(long, help = "Skip pre-request, execute-before and execute-after commands (for untrusted suites)")]
+    no_hooks: bool,
+    
+    /// Explain every condition
+    #[arg(long, help = "Evaluate every condition and print PASS/FAIL with the actual value, not only the first failure")]
+    explain: bool,
+    
+    /// Rewrite fixtures from the actual responses
+    #[arg(long, help = "Rewrite the fixture files of failing equals-file and json-equals-file conditions with the actual values")]
+    update_fixtures: bool,
+    
+    /// Snapshot directory for regression checks
+    #[arg(long, value_name = "DIR", help = "Record each file's response into DIR on the first run and fail later runs whose response differs")]
+    baseline: Option<PathBuf>,
+    
+    /// Re-record the baseline snapshots
+    #[arg(long, requires = "baseline", help = "Overwrite the --baseline snapshots with the current responses")]
+    update_baseline: bool,
+    
+    /// Latency regression guard for the baseline
+    #[arg(long, value_name = "PERCENT", requires = "baseline", value_parser = parse_percentage, help = "With --baseline, fail files whose latency exceeds the recorded latency by more than PERCENT")]
+    latency_regression_threshold: Option<f64>,
+    
+    /// Abort the run after this many failed files
+    #[arg(long, value_name = "N", conflicts_with = "monitor", value_parser = clap::value_parser!(u64).range(1..), help = "Stop the run once N files have failed; files still running or not started are cancelled")]
+    max_failures: Option<u64>,
+    
+    /// Required number of passing files
+    #[arg(long, value_name = "N", conflicts_with = "monitor", help = "Exit with status 1 unless exactly N files passed, instead of requiring every file to pass")]
+    expect_count: Option<usize>,
+    
+    /// Cleanup file run after the suite
+    #[arg(long, value_name = "FILE", conflicts_with = "monitor", help = "Run FILE after all other files, even when they failed, e.g. to delete created test resources")]
+    teardown: Option<PathBuf>,
+    
+    /// Let a failing teardown fail the run
+    #[arg(long, requires = "teardown", help = "Exit with status 1 when the --teardown file fails, not only report it")]
+    fail_on_teardown: bool,
+    
+    /// Accept plaintext requests silently
+    #[arg(long, help = "Do not warn about files sending plaintext requests with is-http: true")]
+    insecure_allow_http: bool,
+    
+    /// Refuse plaintext requests
+    #[arg(long, conflicts_with = "insecure_allow_http", help = "Fail files that send plaintext requests with is-http: true instead of warning")]
+    strict_https: bool,
+    
+    /// Audit log of executed requests
+    #[arg(long, value_name = "FILE", help = "Append one line per executed request (timestamp, file, method, url, status, latency) to FILE")]
+    log_requests: Option<PathBuf>,
+    
+    /// Per-file debugging artifacts
+    #[arg(long, value_name = "DIR", help = "Write each file's resolved request, raw response, headers and result into a folder under DIR mirroring its path")]
+    output_dir: Option<PathBuf>,
+    
+    /// Default options from a config file profile
+    #[arg(long, value_name = "NAME", help = "Use the options of [profiles.NAME] in greq.toml (current directory, then home) as defaults")]
+    profile: Option<String>,
+    
+    /// Target environment for every file
+    #[arg(long, value_name = "URL", value_parser = parse_base_url, help = "Send requests to this scheme and host (plus optional path prefix), keeping each file's path and query")]
+    base_url: Option<reqwest::Url>,
+    
+    /// Cap the request rate
+    #[arg(long, value_name = "N", value_parser = RateLimiter::parse, help = "Send at most N requests per second across all files (fractions allowed)")]
+    rate: Option<RateLimiter>,
+    
+    /// Run the files repeatedly as a synthetic monitor
+    #[arg(long, help = "Run the files every --interval seconds until Ctrl-C, printing one status line per cycle")]
+    monitor: bool,
+    
+    /// Seconds between monitor cycles
+    #[arg(long, value_name = "SECONDS", requires = "monitor", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..), help = "Seconds between the start of two monitor cycles")]
+    interval: u64,
+    
+    /// Stop monitoring after consecutive failures
+    #[arg(long, value_name = "N", requires = "monitor", value_parser = clap::value_parser!(u32).range(1..), help = "Exit with status 1 after N consecutive failed monitor cycles")]
+    fail_after: Option<u32>,
+    
+    /// Console log format
+    #[arg(long, value_name = "FORMAT", value_parser = LogFormat::parse, default_value = "human", help = "Log record format: human or json (one JSON object per line)")]
+    log_format: LogFormat,
+    
+    /// Re-run the files that failed in a previous run
+    #[arg(long, value_name = "RESULTS_JSON", help = "Run only the files that failed according to the results.json written to the --output-dir of a previous run, plus files it has no result for")]
+    rerun_failed: Option<PathBuf>,
+    
+    /// Run only files affected by git changes
+    #[arg(long, help = "Run only the files that changed since --base-ref, or that depend on or extend a changed file")]
+    changed_only: bool,
+    
+    /// Git revision that --changed-only compares against
+    #[arg(long, value_name = "REF", requires = "changed_only", default_value = "HEAD", help = "Git revision to compare with for --changed-only (default: HEAD, i.e. uncommitted changes)")]
+    base_ref: String,
+    
+    /// Print the .greq file grammar
+    #[arg(long, help = "List the supported header properties, methods, condition keys and operators")]
+    help_grammar: bool,
+    
+    /// Write all requests and responses to a HAR file
+    #[arg(long, value_name = "FILE", help = "Record all executed requests and responses into a HAR 1.2 file")]
+    har: Option<PathBuf>,
+    
+    /// Print a curl command for each request
+    #[arg(long, help = "Print an equivalent curl command for each request before it is sent")]
+    show_curl: bool,
+    
+    /// Additional headers to redact in curl commands
+    #[arg(long, value_name = "HEADER", requires = "show_curl", help = "Redact this header in --show-curl commands (authorization, cookie and similar are always redacted)")]
+    curl_redact: Vec<String>,
+    
+    /// Additional headers to redact in the HAR file
+    #[arg(long, value_name = "HEADER", help = "Redact this header in the HAR file (authorization, cookie and similar are always redacted)")]
+    har_redact: Vec<String>,
+    
+    /// Write a JUnit XML report
+    #[arg(long, value_name = "FILE", help = "Write a JUnit XML report with one test suite per project")]
+    junit: Option<PathBuf>,
+    
+    /// Prefix for the JUnit classname
+    #[arg(long, value_name = "PREFIX", requires = "junit", help = "Prepend PREFIX to the project name in JUnit classnames (PREFIX.project)")]
+    junit_classname_prefix: Option<String>,
+    
+    /// How JUnit test case names are derived from file paths
+    #[arg(long, value_name = "MODE", requires = "junit", value_parser = JunitTestName::parse, default_value = "file", help = "JUnit test case name: file (users.greq), stem (users) or path")]
+    junit_test_name: JunitTestName,
+    
+    /// Serve responses from a mock file instead of the network
+    #[arg(long, value_name = "MOCKFILE", help = "Answer requests from a JSON mock file instead of the network")]
+    mock: Option<PathBuf>,
+    
+    /// Override an environment placeholder for every file
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_variable, help = "Override $(environment.KEY) for every file")]
+    set: Vec<(String, String)>,
+    
+    /// Override an environment placeholder for files of one project
+    #[arg(long = "set-for", value_name = "PROJECT:KEY=VALUE", value_parser = parse_project_variable, help = "Override $(environment.KEY) only for files whose header project matches (takes precedence over --set)")]
+    set_for: Vec<(String, (String, String))>,
+}
+
+/// Marks per line of `--progress` output before the completed count is shown
+const PROGRESS_MARKS_PER_LINE: usize = 50;
+
+/// Print `.` for a passing and `F` for a failing file, ending each line of marks (and the last one)
+/// with the number of completed files
+fn print_progress_mark(result: &ExecutionResult, completed: usize, total: usize) {
+    let mark = if result.success { ".".green() } else { "F".red() };
+    print!("{mark}");
+    if completed.is_multiple_of(PROGRESS_MARKS_PER_LINE) || completed == total {
+        println!(" {completed}/{total}");
+    }
+    let _ = io::stdout().flush();
+}
+
+/// Run the `--teardown` file through the single-file path, sharing the `$(vars.*)` extracted by the
+/// suite. Returns whether it passed; a failure is reported but only fails the run with `--fail-on-teardown`
+async fn run_teardown(path: &Path, options: &ExecutionOptions) -> bool {
+    println!("\n{}", "=== Teardown ===".bold().blue());
+    let passed = match execute_greq_file_with_options(path, options).await {
+        Ok(result) => {
+            print_execution_result(&result);
+            result.success
+        },
+        Err(e) => {
+            eprintln!("{} Teardown {} failed: {}", "✗".red(), path.display(), e);
+            false
+        }
+    };
+    if !passed {
+        eprintln!("{} Teardown failed; the exit status is unaffected unless --fail-on-teardown is set", "⚠".yellow());
+    }
+    passed
+}
+
+/// Parse a `KEY=VALUE` command line override
+fn parse_variable(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{value}'")),
+    }
+}
+
+/// Parse a non-negative percentage such as `20` or `12.5`
+fn parse_percentage(value: &str) -> Result<f64, String> {
+    match value.trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent.is_finite() && percent >= 0.0 => Ok(percent),
+        _ => Err(format!("expected a non-negative percentage, got '{value}'")),
+    }
+}
+
+/// Parse a `PROJECT:KEY=VALUE` command line override
+fn parse_project_variable(value: &str) -> Result<(String, (String, String)), String> {
+    match value.split_once(':') {
+        Some((project, variable)) if !project.trim().is_empty() => Ok((project.trim().to_string(), parse_variable(variable)?)),
+        _ => Err(format!("expected PROJECT:KEY=VALUE, got '{value}'")),
+    }
+}
+
+/// Parse the command line again with the options of a profile in front of the given ones
+fn apply_profile(name: &str) -> Cli {
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let Some(config_path) = find_config_file(&current_dir, dirs::home_dir().as_deref()) else {
+        eprintln!("{} --profile {}: no greq.toml in the current or home directory", "✗".red(), name);
+        process::exit(1);
+    };
+    let profile_args = match load_profile_args(&config_path, name) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red(), e);
+            process::exit(1);
+        }
+    };
+
+    let known: Vec<String> = Cli::command()
+        .get_arguments()
+        .filter_map(|arg| arg.get_long().map(|long| format!("--{long}")))
+        .collect();
+    if let Some(unknown) = profile_args.iter().find(|arg| arg.starts_with("--") && !known.contains(arg)) {
+        eprintln!("{} {}: profile '{}' sets unknown option '{}'", "✗".red(), config_path.display(), name, unknown.trim_start_matches('-'));
+        process::exit(1);
+    }
+
+    let mut args = std::env::args_os();
+    let program = args.next().unwrap_or_else(|| "greq".into());
+    Cli::parse_from(std::iter::once(program).chain(profile_args.into_iter().map(Into::into)).chain(args))
 }
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
+    let cli = match cli.profile.clone() {
+        Some(name) => apply_profile(&name),
+        None => cli,
+    };
+    
+    if cli.help_grammar {
+        print_grammar();
+        process::exit(0);
+    }
     
     // Set log level based on verbose flag before logger initialization
     if cli.verbose {
@@ -28,7 +328,7 @@ async fn main() {
     }
     
     // Initialize logger
-    if let Err(e) = logger::init_logger() {
+    if let Err(e) = logger::init_logger_with_format(cli.log_format) {
         eprintln!("Failed to initialize logger: {e}");
         process::exit(1);
     }
@@ -37,7 +337,7 @@ async fn main() {
     println!("{}", "=".repeat(30).blue());
     
     // Validate files exist
-    for file_path in &cli.files {
+    for file_path in cli.files.iter().chain(&cli.teardown) {
         if !file_path.exists() {
             eprintln!("{} File not found: {}", "✗".red(), file_path.display());
             process::exit(1);
@@ -48,21 +348,221 @@ async fn main() {
         }
     }
     
-    // Execute files
-    match execute_multiple_greq_files(&cli.files, cli.verbose).await {
-        Ok(results) => {
-            print_execution_results(&results);
-            
-            // Exit with appropriate code
-            if all_successful(&results) {
-                process::exit(0);
-            } else {
+    let mock = match cli.mock.as_ref().map(MockResponses::load).transpose() {
+        Ok(mock) => mock.map(Arc::new),
+        Err(e) => {
+            eprintln!("{} {}", "✗".red(), e);
+            process::exit(1);
+        }
+    };
+    
+    let request_log = match &cli.log_requests {
+        Some(path) => match RequestLog::open(path) {
+            Ok(request_log) => Some(request_log),
+            Err(e) => {
+                eprintln!("{} Failed to open request log {}: {}", "✗".red(), path.display(), e);
                 process::exit(1);
             }
         },
-        Err(e) => {
-            eprintln!("{} Execution failed: {}", "✗".red(), e);
-            process::exit(1);
+        None => None,
+    };
+    
+    let options = ExecutionOptions {
+        verbose: cli.verbose,
+        no_retry: cli.no_retry,
+        max_response_size: cli.max_response_size,
+        truncate_large_responses: cli.truncate_large_responses,
+        har: cli.har.as_ref().map(|_| HarRecorder::new(&cli.har_redact)),
+        mock,
+        variables: cli.set.iter().cloned().collect(),
+        project_variables: cli.set_for.iter().fold(HashMap::new(), |mut scoped, (project, (key, value))| {
+            scoped.entry(project.clone()).or_insert_with(HashMap::new).insert(key.clone(), value.clone());
+            scoped
+        }),
+        only_conditions: cli.only.clone(),
+        environment: cli.env.clone(),
+        max_failures: cli.max_failures.map(|max| max as usize),
+        request_id_header: (!cli.no_request_id).then(|| cli.request_id_header.clone()),
+        no_hooks: cli.no_hooks,
+        update_fixtures: cli.update_fixtures,
+        explain: cli.explain,
+        show_curl: cli.show_curl,
+        quiet: cli.progress,
+        curl_redact: cli.curl_redact.clone(),
+        rate_limiter: cli.rate.clone(),
+        base_url: cli.base_url.clone(),
+        suite_variables: SuiteVariables::default(),
+        baseline: cli.baseline.as_ref().map(|dir| {
+            let baseline = Baseline::new(dir, cli.update_baseline);
+            match cli.latency_regression_threshold {
+                Some(percent) => baseline.with_latency_threshold(percent),
+                None => baseline,
+            }
+        }),
+        artifacts: cli.output_dir.as_ref().map(ArtifactWriter::new),
+        request_log,
+        allow_insecure_http: cli.insecure_allow_http,
+        strict_https: cli.strict_https,
+    };
+    
+    let files = if cli.changed_only {
+        match git_changed_files(&cli.base_ref, Path::new(".")) {
+            Some(changed) => {
+                let selected = select_changed_files(&cli.files, &changed, &options);
+                println!("Running {} of {} files affected by changes since {}", selected.len(), cli.files.len(), cli.base_ref);
+                if selected.is_empty() {
+                    process::exit(0);
+                }
+                selected
+            },
+            None => {
+                eprintln!("{} --changed-only: cannot read git changes since {}, running all files", "⚠".yellow(), cli.base_ref);
+                cli.files.clone()
+            }
+        }
+    } else {
+        cli.files.clone()
+    };
+    
+    let files = match &cli.rerun_failed {
+        Some(results_path) => {
+            let previous = load_run_results(results_path).unwrap_or_else(|e| {
+                eprintln!("{} --rerun-failed: cannot read {}: {}", "✗".red(), results_path.display(), e);
+                process::exit(1);
+            });
+            let (selected, without_result) = select_failed_files(&previous, &files);
+            if !without_result.is_empty() {
+                println!(
+                    "{} {} files have no result in {} and run as well: {}",
+                    "⚠".yellow(),
+                    without_result.len(),
+                    results_path.display(),
+                    without_result.iter().map(|file| file.display().to_string()).collect::<Vec<_>>().join(", ")
+                );
+            }
+            println!("Re-running {} of {} files that failed or were not run in {}", selected.len(), files.len(), results_path.display());
+            if selected.is_empty() {
+                process::exit(0);
+            }
+            selected
+        },
+        None => files,
+    };
+    
+    if cli.monitor {
+        let settings = MonitorSettings {
+            interval: Duration::from_secs(cli.interval),
+            fail_after: cli.fail_after,
+        };
+        let (_, stop) = run_monitor(&files, &options, &settings).await;
+        process::exit(if stop == MonitorStop::TooManyFailures { 1 } else { 0 });
+    }
+    
+    // Execute files, printing each result (or a progress mark) as soon as its file completes
+    if !cli.summary_only && !cli.progress && !cli.group_by_project {
+        print_results_heading();
+    }
+    let mut results = Vec::new();
+    let mut execution_error = None;
+    let started_at = Utc::now();
+    {
+        // Dropping the stream cancels the files still running, so none of them overlaps the teardown
+        let mut stream = pin!(stream_greq_files_with_options(&files, &options));
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(result) => {
+                    if cli.progress {
+                        print_progress_mark(&result, results.len() + 1, files.len());
+                    } else if !cli.summary_only && !cli.group_by_project {
+                        print_execution_result(&result);
+                    }
+                    results.push(result);
+                },
+                Err(e) => {
+                    execution_error = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+    
+    // A run stopped early leaves the line of progress marks open
+    if cli.progress && results.len() < files.len() && !results.len().is_multiple_of(PROGRESS_MARKS_PER_LINE) {
+        println!(" {}/{}", results.len(), files.len());
+    }
+    
+    // The teardown cleans up whether the suite passed, failed or was aborted
+    let teardown_passed = match &cli.teardown {
+        Some(path) => run_teardown(path, &options).await,
+        None => true,
+    };
+    if let Some(e) = execution_error {
+        eprintln!("{} Execution failed: {}", "✗".red(), e);
+        process::exit(1);
+    }
+    
+    if let Some(max) = cli.max_failures.filter(|_| results.len() < files.len()) {
+        eprintln!(
+            "{} Stopped after {} failed files (--max-failures {}); {} of {} files were not run",
+            "✗".red(), max, max, files.len() - results.len(), files.len()
+        );
+    }
+    
+    // Reports list the files in command-line order
+    results.sort_by_key(|result| files.iter().position(|file| file.display().to_string() == result.file_path));
+    if cli.group_by_project {
+        print_results_by_project(&results);
+    } else if cli.progress {
+        print_results_heading();
+        for result in &results {
+            print_execution_result(result);
         }
     }
+    
+    if let Some(artifacts) = &options.artifacts {
+        if let Err(e) = artifacts.write_run_results(&results) {
+            eprintln!("{} Failed to write the run results to the output directory: {}", "✗".red(), e);
+        }
+    }
+    
+    if let (Some(har_path), Some(recorder)) = (&cli.har, &options.har) {
+        if let Err(e) = recorder.write_to(har_path) {
+            eprintln!("{} Failed to write HAR file {}: {}", "✗".red(), har_path.display(), e);
+        }
+    }
+    
+    if let Some(junit_path) = &cli.junit {
+        let report = JunitReport {
+            classname_prefix: cli.junit_classname_prefix.clone(),
+            test_name: cli.junit_test_name,
+            started_at: Some(started_at),
+        };
+        if let Err(e) = report.write_to(&results, junit_path) {
+            eprintln!("{} Failed to write JUnit report {}: {}", "✗".red(), junit_path.display(), e);
+        }
+    }
+    
+    if cli.summary_only {
+        print_execution_summary(&results);
+    } else {
+        print_execution_totals(&results);
+    }
+    
+    // Exit with appropriate code; --expect-count makes the number of passing files the contract
+    let succeeded = match cli.expect_count {
+        Some(expected) => {
+            let passed = count_passed(&results);
+            if passed != expected {
+                eprintln!("{} --expect-count: {} of {} files passed, expected exactly {}", "✗".red(), passed, results.len(), expected);
+            }
+            passed == expected
+        },
+        None => all_successful(&results),
+    };
+    let succeeded = succeeded && (teardown_passed || !cli.fail_on_teardown);
+    if succeeded {
+        process::exit(0);
+    } else {
+        process::exit(1);
+    }
 }
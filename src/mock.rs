@@ -0,0 +1,167 @@
+use crate::error::{GreqError, Result};
+use crate::models::{GreqFile, Response};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// A canned response from a mock file
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockEntry {
+    /// HTTP method to match; any method when omitted or `*`
+    #[serde(default)]
+    pub method: Option<String>,
+    /// URI pattern where `*` matches any characters. Without `?` the query string is ignored
+    pub uri: String,
+    #[serde(default = "default_mock_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Response body; strings are used as-is, other JSON values are serialized
+    #[serde(default)]
+    pub body: Option<Value>,
+}
+
+fn default_mock_status() -> u16 {
+    200
+}
+
+/// Canned responses used instead of the network by `--mock`
+#[derive(Debug, Clone)]
+pub struct MockResponses {
+    source: String,
+    entries: Vec<(MockEntry, Regex)>,
+}
+
+impl MockResponses {
+    /// Load a mock file: a JSON array of entries, first match wins
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let source = path.display().to_string();
+        let content = fs::read_to_string(path)
+            .map_err(|_| GreqError::FileNotFound(source.clone()))?;
+        let entries: Vec<MockEntry> = serde_json::from_str(&content)
+            .map_err(|e| GreqError::Parse(format!("{source}: Invalid mock file: {e}"), None))?;
+
+        Self::from_entries(entries, source)
+    }
+
+    /// Build mock responses from already parsed entries
+    pub fn from_entries(entries: Vec<MockEntry>, source: String) -> Result<Self> {
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let pattern = format!("^{}$", regex::escape(&entry.uri).replace(r"\*", ".*"));
+                Ok((entry, Regex::new(&pattern)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { source, entries })
+    }
+
+    /// Return the canned response for the request, or an error when nothing matches
+    pub fn respond(&self, greq_file: &GreqFile) -> Result<Response> {
+        let method = &greq_file.content.request_line.method;
+        let uri = &greq_file.content.request_line.uri;
+        let path = uri.split('?').next().unwrap_or(uri);
+
+        let (entry, _) = self
+            .entries
+            .iter()
+            .find(|(entry, pattern)| {
+                let method_matches = entry
+                    .method
+                    .as_ref()
+                    .is_none_or(|m| m == "*" || m.eq_ignore_ascii_case(method));
+                let target = if entry.uri.contains('?') { uri } else { path };
+                method_matches && pattern.is_match(target)
+            })
+            .ok_or_else(|| {
+                GreqError::Validation(format!("No mock in {} matches {} {}", self.source, method, uri))
+            })?;
+
+        log::debug!("Serving mock response for {} {} from {}", method, uri, self.source);
+
+        let headers: HashMap<String, String> = entry
+            .headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect();
+        let body = match &entry.body {
+            Some(Value::String(text)) => text.clone(),
+            Some(value) => serde_json::to_string(value)?,
+            None => String::new(),
+        };
+
+        Ok(Response {
+            status_code: entry.status,
+            all_headers: headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            content_encoding: Response::content_encoding_from(&headers),
+            headers,
+            body,
+            latency: Duration::ZERO,
+            attempts: 1,
+            ..Response::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Content, Footer, Header, RequestLine};
+    use tempfile::tempdir;
+
+    fn create_greq_file(method: &str, uri: &str) -> GreqFile {
+        GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: method.to_string(),
+                    uri: uri.to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                headers: HashMap::new(),
+                body: None,
+            },
+            footer: Footer::default(),
+            file_path: "test.greq".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mock_matching() {
+        let dir = tempdir().unwrap();
+        let mock_path = dir.path().join("mocks.json");
+        fs::write(
+            &mock_path,
+            r#"[
+                {"method": "POST", "uri": "/users", "status": 201, "body": {"id": 7}},
+                {"uri": "/users/*", "headers": {"Content-Type": "application/json"}, "body": "{\"name\": \"Ann\"}"}
+            ]"#,
+        )
+        .unwrap();
+        let mocks = MockResponses::load(&mock_path).unwrap();
+
+        let created = mocks.respond(&create_greq_file("POST", "/users")).unwrap();
+        assert_eq!(created.status_code, 201);
+        assert_eq!(created.body, r#"{"id":7}"#);
+
+        let fetched = mocks.respond(&create_greq_file("GET", "/users/7?fields=name")).unwrap();
+        assert_eq!(fetched.status_code, 200);
+        assert_eq!(fetched.headers.get("content-type"), Some(&"application/json".to_string()));
+        assert_eq!(fetched.body, r#"{"name": "Ann"}"#);
+    }
+
+    #[test]
+    fn test_unmatched_request_errors() {
+        let mocks = MockResponses::from_entries(Vec::new(), "mocks.json".to_string()).unwrap();
+
+        let result = mocks.respond(&create_greq_file("DELETE", "/users/7"));
+
+        assert!(result.unwrap_err().to_string().contains("No mock in mocks.json matches DELETE /users/7"));
+    }
+}
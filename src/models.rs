@@ -1,4 +1,15 @@
+use crate::artifacts::ArtifactWriter;
+use crate::baseline::Baseline;
+use crate::har::HarRecorder;
+use crate::json_path::get_path;
+use crate::mock::MockResponses;
+use crate::rate_limit::RateLimiter;
+use crate::request_log::RequestLog;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
 /// Represents a complete Greq test file with all its sections
@@ -10,6 +21,21 @@ pub struct GreqFile {
     pub file_path: String,
 }
 
+impl GreqFile {
+    /// Full URL of the request, from `is-http`, the `host` header and the request line;
+    /// `protocol: websocket` files get a `ws`/`wss` URL
+    pub fn request_url(&self) -> String {
+        let scheme = match (self.header.is_websocket, self.header.is_http) {
+            (false, true) => "http",
+            (false, false) => "https",
+            (true, true) => "ws",
+            (true, false) => "wss",
+        };
+        let host = self.content.headers.get("host").map(String::as_str).unwrap_or_default();
+        format!("{}://{}{}", scheme, host, self.content.request_line.uri)
+    }
+}
+
 /// Header section containing metadata and execution properties
 #[derive(Debug, Clone)]
 pub struct Header {
@@ -25,6 +51,74 @@ pub struct Header {
     pub execute_before: Option<String>,
     pub execute_after: Option<String>,
     pub set_environment: HashMap<String, String>,
+    pub strict: bool,
+    pub error_envelope_path: Option<String>,
+    pub client_cert: Option<String>,
+    pub client_key: Option<String>,
+    pub client_identity: Option<String>,
+    pub client_identity_password: Option<String>,
+    /// Conditions declared inline with `expect:`; moved into the footer once the file is parsed
+    pub expect: Vec<Condition>,
+    /// Set by `protocol: websocket`; the request is sent as a WebSocket upgrade
+    pub is_websocket: bool,
+    /// Number of frames to read from a WebSocket before evaluating conditions
+    pub websocket_frames: usize,
+    /// `gzip` or `deflate`; the request body is compressed before sending
+    pub compress_body: Option<String>,
+    /// `merge` or `json-patch`; PATCH requests get the matching content type and a body shape check
+    pub patch_type: Option<String>,
+    /// `json` or `xml`; the request body must parse as this format before it is sent
+    pub validate_body: Option<String>,
+    /// Fields from `form:` lines, URL-encoded into the request body before sending
+    pub form: Vec<(String, String)>,
+    /// `json` deep-merges this file's JSON body into the body of the file it extends; `replace` by default
+    pub body_merge: Option<String>,
+    /// JSON file of named snippets inserted with `{{> name}}` in header values and the body
+    pub includes_file: Option<String>,
+    /// Response status codes that are retried like network errors, honoring `Retry-After`
+    pub retry_on_status: Vec<u16>,
+    /// Response header read by `cache-status` instead of the recognized provider headers
+    pub cache_header: Option<String>,
+    /// Command that receives the resolved request as JSON on stdin and prints header/body overrides
+    pub pre_request: Option<String>,
+    /// `resolve: host=address` DNS overrides; the URL and `host` header keep the original name
+    pub resolve: Vec<(String, IpAddr)>,
+    /// Local IP address the connection is made from
+    pub local_address: Option<IpAddr>,
+    /// Longest a DNS lookup may take before the request fails
+    pub dns_timeout: Option<Duration>,
+    /// Address family the connection is restricted to (`ip-version`)
+    pub ip_version: IpVersion,
+    /// Snapshot paths (`status`, `headers.<name>`, `body.<path>`) that `--baseline` does not compare
+    pub baseline_ignore: Vec<String>,
+    /// Keep this file's scheme and host when `--base-url` is given
+    pub ignore_base_url: bool,
+    /// Pause before sending the request (`delay-before`, in milliseconds)
+    pub delay_before: Option<Duration>,
+    /// Pause after the response arrived, before the next file of the chain runs (`delay-after`)
+    pub delay_after: Option<Duration>,
+    /// Negative test: an error status is expected, so output does not flag it. Pass/fail still comes from the footer
+    pub expect_failure: bool,
+}
+
+/// `ip-version` preference: `auto` connects to any resolved address, `v4`/`v6` only to that family
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IpVersion {
+    #[default]
+    Auto,
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    /// Name of the address family for messages
+    pub fn family(self) -> &'static str {
+        match self {
+            IpVersion::Auto => "IP",
+            IpVersion::V4 => "IPv4",
+            IpVersion::V6 => "IPv6",
+        }
+    }
 }
 
 /// Content section representing the HTTP request
@@ -47,28 +141,116 @@ pub struct RequestLine {
 #[derive(Debug, Clone, Default)]
 pub struct Footer {
     pub conditions: Vec<Condition>,
+    /// Keys from `print:` lines whose values are logged before the conditions are evaluated
+    pub prints: Vec<ConditionKey>,
+    /// `transform` lines applied in order to a copy of the response body before evaluation
+    pub transforms: Vec<BodyTransform>,
+    /// Resolved paths of the `include-conditions:` files whose conditions were merged in
+    pub included_files: Vec<String>,
+    /// `extract <name>: <key>` lines; a passing file stores the values for `$(vars.<name>)` in later files
+    pub extracts: Vec<Extract>,
+}
+
+/// An `extract <name>: <key>` footer line
+#[derive(Debug, Clone)]
+pub struct Extract {
+    pub name: String,
+    pub key: ConditionKey,
+}
+
+/// A `transform response-body.<path>: <kind>` footer line
+#[derive(Debug, Clone)]
+pub struct BodyTransform {
+    /// JSON path of the value to replace; empty for the whole body
+    pub path: String,
+    pub kind: TransformKind,
+}
+
+/// How a `transform` line decodes a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransformKind {
+    Base64Decode,
+    UrlDecode,
+    /// Parse a string holding JSON text into the JSON value
+    JsonParse,
 }
 
 /// A single validation condition
 #[derive(Debug, Clone)]
 pub struct Condition {
     pub is_or: bool,
+    /// Set by the `and` prefix; joins the previous condition's group in failure messages
+    pub is_and: bool,
     pub is_not: bool,
     pub key: ConditionKey,
     pub operator: Operator,
     pub case_sensitive: bool,
     pub value: String,
+    /// Optional `[name]` tag used by `--only` to evaluate selected conditions
+    pub name: Option<String>,
+    /// `[env:prod,staging]` guard; the condition is skipped unless `--env` names one of these
+    pub environments: Vec<String>,
 }
 
 /// The key part of a condition (what to evaluate)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConditionKey {
     StatusCode,
     Headers,
     Header(String),
+    /// `trailers.<name>`: a trailing header sent after the body
+    Trailer(String),
     ResponseBody,
     ResponseBodyPath(String),
     Latency,
+    Cookie(String),
+    /// `location.query.<param>`: a URL-decoded query parameter of the `Location` header
+    LocationQuery(String),
+    ErrorCode,
+    ErrorMessage,
+    Frame(usize),
+    /// `part[n].body`: body of the n-th part (0-based) of a multipart response
+    PartBody(usize),
+    /// `part[n].headers.<name>`: a header of the n-th part of a multipart response
+    PartHeader(usize, String),
+    Charset,
+    /// `content-language`: the `Content-Language` header, for i18n suites
+    ContentLanguage,
+    /// `vary`: the `Vary` header, repeated headers joined with `, `
+    Vary,
+    HeaderCount,
+    /// `header-count.<name>`: how often a header occurred, counting repeated headers like set-cookie
+    HeaderOccurrences(String),
+    CacheStatus,
+    /// `redirect-count`: number of redirects followed before the final response
+    RedirectCount,
+    /// `final-url`: URL of the final response after redirects
+    FinalUrl,
+    /// `attempts`: requests sent until the response was accepted, including retries
+    Attempts,
+    /// `scheme`: scheme of the final URL after redirects (`http` or `https`)
+    Scheme,
+    /// `compressed`: whether the response body was sent with a `Content-Encoding` such as gzip or br
+    Compressed,
+    /// `from-cache`: whether the response was served from a local cache instead of the network
+    FromCache,
+    /// `tls.<property>`: a property of the server certificate, e.g. `tls.days-until-expiry`
+    Tls(String),
+    /// `time.connect`, `time.ttfb` or `time.download` in milliseconds
+    Time(String),
+    /// `response-body.<path> any|all .<element-path>`: the elements of a JSON array checked against the condition
+    ResponseBodyElements {
+        path: String,
+        quantifier: Quantifier,
+        element_path: String,
+    },
+}
+
+/// Whether any or all elements of an array must satisfy a condition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantifier {
+    Any,
+    All,
 }
 
 /// Comparison operators for conditions
@@ -76,6 +258,8 @@ pub enum ConditionKey {
 pub enum Operator {
     Equals,
     Contains,
+    /// Contains at least one of a comma-separated list of substrings
+    ContainsAny,
     MatchesRegex,
     LessThan,
     LessThanOrEqual,
@@ -84,25 +268,259 @@ pub enum Operator {
     StartsWith,
     EndsWith,
     Exists,
+    Within,
+    /// Numeric equality within a tolerance (`19.99±0.01`); a tiny default tolerance when omitted
+    ApproxEquals,
+    /// Both sides parsed as JSON and compared as values, ignoring key order and whitespace
+    JsonEquals,
+    /// The actual JSON has every key and value of the expected JSON; extra object keys are ignored
+    JsonContains,
+    /// Equal to the content of a fixture file, resolved relative to the `.greq` file
+    EqualsFile,
+    /// `json-equals` against the content of a fixture file
+    JsonEqualsFile,
+    /// Byte-for-byte equal to a fixture file; `response-body` is compared as raw bytes
+    EqualsBinaryFile,
+    /// The JSON value at a response-body path is a string, number, boolean, array, object or null
+    TypeEquals,
+    /// A JSON array whose elements are in ascending order (`true`) or not (`false`)
+    SortedAscending,
+    /// A JSON array whose elements are in descending order (`true`) or not (`false`)
+    SortedDescending,
+    /// A JSON array whose elements equal a comma-separated list, in order
+    EqualsSequence,
 }
 
 /// HTTP response data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Response {
     pub status_code: u16,
     pub headers: HashMap<String, String>,
+    /// Every response header in received order, including repeated ones like set-cookie
+    pub all_headers: Vec<(String, String)>,
     pub body: String,
     pub latency: Duration,
+    /// Text of the frames received over a WebSocket, in order
+    pub frames: Vec<String>,
+    /// Server certificate of an HTTPS request; `None` for plaintext HTTP
+    pub tls_certificate: Option<PeerCertificate>,
+    /// Negotiated TLS protocol version, `1.2` or `1.3`; only recorded when a condition uses `tls.version`
+    pub tls_version: Option<String>,
+    /// Phases of the final attempt; `None` for mocked and WebSocket responses
+    pub timings: Option<ResponseTimings>,
+    /// Trailing headers (lowercased names); `None` when the response had no trailer section,
+    /// as for HTTP/1.1 responses that are not chunked
+    pub trailers: Option<HashMap<String, String>>,
+    /// `Content-Encoding` the body was sent with (e.g. `gzip`), kept after the body is decompressed;
+    /// `None` for an uncompressed body
+    pub content_encoding: Option<String>,
+    /// URLs the request was redirected to, in order; empty when the first response was final
+    pub redirects: Vec<String>,
+    /// URL of the final response after redirects; `None` for mocked and WebSocket responses
+    pub final_url: Option<String>,
+    /// Body bytes as received (after decompression), before charset decoding into `body`;
+    /// `None` for mocked and WebSocket responses, whose body is text
+    pub raw_body: Option<Vec<u8>>,
+    /// Requests sent until this response was accepted: 1 when no retry was needed.
+    /// Mocked and WebSocket responses count as a single attempt
+    pub attempts: u32,
+    /// Served from a local cache instead of the network. greq has no client-side cache,
+    /// so every response, including mocked ones, is `false`
+    pub from_cache: bool,
+    /// The body parsed as JSON, filled on the first call to `json()`
+    pub(crate) json_cache: OnceLock<Option<Value>>,
+}
+
+impl Response {
+    /// The body as bytes: the raw bytes when captured, otherwise the text body
+    pub fn body_bytes(&self) -> &[u8] {
+        self.raw_body.as_deref().unwrap_or(self.body.as_bytes())
+    }
+
+    /// The `content_encoding` of a response with these (lowercased) headers
+    pub fn content_encoding_from(headers: &HashMap<String, String>) -> Option<String> {
+        headers.get("content-encoding")
+            .map(|encoding| encoding.trim().to_lowercase())
+            .filter(|encoding| !encoding.is_empty() && encoding != "identity")
+    }
+
+    /// The body parsed as JSON, or `None` when it is not valid JSON. The body is parsed once and cached
+    pub fn json(&self) -> Option<&Value> {
+        self.json_cache
+            .get_or_init(|| serde_json::from_str(&self.body).ok())
+            .as_ref()
+    }
+
+    /// The value at a JSON path (`users[0].name`) in the body, or `None` when the body is not JSON or the path does not exist
+    pub fn get_path(&self, path: &str) -> Option<Value> {
+        get_path(self.json()?, path)
+    }
+}
+
+/// Where the time of a request went, for `time.<phase>` conditions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseTimings {
+    /// DNS lookup, TCP connect and TLS handshake; zero when a pooled connection was reused
+    pub connect: Duration,
+    /// From sending the request until the response headers arrived, including `connect`
+    pub ttfb: Duration,
+    /// Reading the response body
+    pub download: Duration,
+}
+
+/// The server certificate presented during the TLS handshake
+#[derive(Debug, Clone)]
+pub struct PeerCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub not_after: DateTime<Utc>,
+}
+
+/// Default limit for response bodies (50 MB)
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 50 * 1024 * 1024;
+
+/// Run-wide options set from the command line that apply to every executed file
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionOptions {
+    pub verbose: bool,
+    /// Make a single attempt per request regardless of `number-of-retries`
+    pub no_retry: bool,
+    /// Records every request that received a response for `--har`
+    pub har: Option<HarRecorder>,
+    /// Canned responses served instead of the network for `--mock`
+    pub mock: Option<Arc<MockResponses>>,
+    /// Largest response body to read; `DEFAULT_MAX_RESPONSE_SIZE` when not set
+    pub max_response_size: Option<usize>,
+    /// Cut oversized bodies at the limit with a warning instead of failing the request
+    pub truncate_large_responses: bool,
+    /// `--set KEY=VALUE` overrides for `$(environment.KEY)` placeholders
+    pub variables: HashMap<String, String>,
+    /// `--set-for PROJECT:KEY=VALUE` overrides that only apply to files of that project
+    pub project_variables: HashMap<String, HashMap<String, String>>,
+    /// `--only` condition names; when set, only these conditions of the executed files are evaluated
+    pub only_conditions: Vec<String>,
+    /// `--env` name; conditions guarded with `[env:...]` for other environments are skipped
+    pub environment: Option<String>,
+    /// `--max-failures`: stop streaming results and cancel the remaining files after this many failed
+    pub max_failures: Option<usize>,
+    /// Header carrying a generated request id (e.g. `X-Request-Id`); no id is sent when `None`
+    pub request_id_header: Option<String>,
+    /// Skip `pre-request`, `execute-before` and `execute-after` commands for untrusted suites
+    pub no_hooks: bool,
+    /// Rewrite the fixture files of failing `equals-file`/`json-equals-file` conditions with the actual values
+    pub update_fixtures: bool,
+    /// Shared `--rate` limiter gating every outgoing request
+    pub rate_limiter: Option<RateLimiter>,
+    /// Response snapshots compared or recorded for `--baseline`
+    pub baseline: Option<Baseline>,
+    /// Do not warn about plaintext `is-http: true` requests (`--insecure-allow-http`)
+    pub allow_insecure_http: bool,
+    /// Refuse plaintext `is-http: true` requests instead of warning (`--strict-https`)
+    pub strict_https: bool,
+    /// Per-file request, response and result artifacts for `--output-dir`
+    pub artifacts: Option<ArtifactWriter>,
+    /// Append-only `--log-requests` file receiving one line per executed request
+    pub request_log: Option<RequestLog>,
+    /// Print a PASS/FAIL line with the actual value for every condition of every file
+    pub explain: bool,
+    /// Print an equivalent `curl` command before each HTTP request is sent (`--show-curl`)
+    pub show_curl: bool,
+    /// Leave out the lines printed while a file runs (`print:` values, updated fixtures and recorded
+    /// baselines) so they do not break into `--progress` marks
+    pub quiet: bool,
+    /// `--curl-redact` headers hidden in `--show-curl` commands besides authorization, cookie and similar
+    pub curl_redact: Vec<String>,
+    /// `--base-url` replacing the scheme and host of every file that does not set `ignore-base-url`
+    pub base_url: Option<reqwest::Url>,
+    /// Values extracted by the files of this run, shared by all of them
+    pub suite_variables: SuiteVariables,
+}
+
+/// Values stored by `extract` lines and read back as `$(vars.<name>)` by the files that run later.
+/// Clones share the same values, so one instance spans a whole invocation
+#[derive(Debug, Clone, Default)]
+pub struct SuiteVariables {
+    values: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl SuiteVariables {
+    pub fn set(&self, name: &str, value: String) {
+        self.values.lock().unwrap_or_else(|e| e.into_inner()).insert(name.to_string(), value);
+    }
+
+    /// The values stored so far
+    pub fn snapshot(&self) -> HashMap<String, String> {
+        self.values.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl ExecutionOptions {
+    /// Variable overrides for a file: project-scoped values take precedence over global ones
+    pub fn variables_for(&self, project: Option<&str>) -> HashMap<String, String> {
+        let mut variables = self.variables.clone();
+        if let Some(scoped) = project.and_then(|project| self.project_variables.get(project)) {
+            variables.extend(scoped.iter().map(|(key, value)| (key.clone(), value.clone())));
+        }
+        variables
+    }
 }
 
 /// Execution result for a single Greq file
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ExecutionResult {
     pub file_path: String,
+    /// `project` header of the file, after inheritance; `None` when it has none or could not be parsed
+    pub project: Option<String>,
     pub success: bool,
     pub response: Option<Response>,
     pub failed_conditions: Vec<String>,
     pub error: Option<String>,
+    /// Request id sent with the file's request, for correlating it with server logs
+    pub request_id: Option<String>,
+    /// Every evaluated condition of the file with its outcome; empty when no response was evaluated
+    pub condition_outcomes: Vec<ConditionOutcome>,
+    /// The file sets `expect-failure: true`, so an error status is the outcome it tests for
+    pub expect_failure: bool,
+    /// The file's request as it was sent; `None` when it never got that far
+    pub request: Option<SentRequest>,
+}
+
+/// Method and full URL of a request as it was sent, after placeholders and the `pre-request` hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentRequest {
+    pub method: String,
+    pub url: String,
+}
+
+#[cfg(test)]
+impl ExecutionResult {
+    /// A result for `file_path` with every other field empty, for tests of result consumers
+    pub(crate) fn for_file(file_path: &str, success: bool) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            success,
+            ..Self::default()
+        }
+    }
+}
+
+/// Structured outcome of one evaluated condition, for consumers that should not parse
+/// the formatted `failed_conditions` messages
+#[derive(Debug, Clone)]
+pub struct ConditionOutcome {
+    pub key: ConditionKey,
+    pub operator: Operator,
+    /// The condition is negated with `not`
+    pub negated: bool,
+    /// Expected value after placeholders were replaced
+    pub expected: String,
+    /// Value the condition saw; `None` when it could not be evaluated
+    pub actual: Option<String>,
+    /// Why the condition could not be evaluated, e.g. a missing JSON path
+    pub error: Option<String>,
+    pub passed: bool,
+    /// The `[name]` tag of the condition
+    pub name: Option<String>,
 }
 
 impl Default for Header {
@@ -120,6 +538,33 @@ impl Default for Header {
             execute_before: None,
             execute_after: None,
             set_environment: HashMap::new(),
+            strict: false,
+            error_envelope_path: None,
+            client_cert: None,
+            client_key: None,
+            client_identity: None,
+            client_identity_password: None,
+            expect: Vec::new(),
+            is_websocket: false,
+            websocket_frames: 1,
+            compress_body: None,
+            patch_type: None,
+            validate_body: None,
+            form: Vec::new(),
+            body_merge: None,
+            includes_file: None,
+            retry_on_status: Vec::new(),
+            cache_header: None,
+            pre_request: None,
+            resolve: Vec::new(),
+            local_address: None,
+            dns_timeout: None,
+            ip_version: IpVersion::Auto,
+            baseline_ignore: Vec::new(),
+            ignore_base_url: false,
+            delay_before: None,
+            delay_after: None,
+            expect_failure: false,
         }
     }
 }
@@ -0,0 +1,155 @@
+use crate::executor::execute_multiple_greq_files_with_options;
+use crate::models::{ExecutionOptions, ExecutionResult};
+use chrono::{SecondsFormat, Utc};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Settings of `--monitor` mode
+#[derive(Debug, Clone)]
+pub struct MonitorSettings {
+    /// Time between the start of two cycles
+    pub interval: Duration,
+    /// Stop after this many consecutive failed cycles
+    pub fail_after: Option<u32>,
+}
+
+/// Why the monitor loop ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MonitorStop {
+    Interrupted,
+    TooManyFailures,
+}
+
+/// Pass/fail counts over the cycles of a monitor run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonitorTally {
+    pub cycles: u32,
+    pub passed: u32,
+    pub failed: u32,
+    pub consecutive_failures: u32,
+}
+
+impl MonitorTally {
+    /// Count a cycle; a cycle passes when every file passed
+    pub fn record(&mut self, passed: bool) {
+        self.cycles += 1;
+        if passed {
+            self.passed += 1;
+            self.consecutive_failures = 0;
+        } else {
+            self.failed += 1;
+            self.consecutive_failures += 1;
+        }
+    }
+
+    /// The final tally, e.g. `12 cycles: 11 passed, 1 failed (91.7% passing)`
+    pub fn summary(&self) -> String {
+        let passing = if self.cycles == 0 {
+            0.0
+        } else {
+            f64::from(self.passed) * 100.0 / f64::from(self.cycles)
+        };
+        format!(
+            "{} cycles: {} passed, {} failed ({:.1}% passing)",
+            self.cycles, self.passed, self.failed, passing
+        )
+    }
+}
+
+/// Run the files every `interval` until Ctrl-C or until `fail_after` consecutive cycles fail,
+/// printing one status line per cycle and the final tally
+pub async fn run_monitor(files: &[PathBuf], options: &ExecutionOptions, settings: &MonitorSettings) -> (MonitorTally, MonitorStop) {
+    let mut tally = MonitorTally::default();
+
+    let stop = loop {
+        let started = Instant::now();
+        let cycle = tokio::select! {
+            results = execute_multiple_greq_files_with_options(files, options) => results,
+            _ = tokio::signal::ctrl_c() => break MonitorStop::Interrupted,
+        };
+
+        let (passed, status) = match cycle {
+            Ok(results) => (results.iter().all(|r| r.success), format_cycle_status(&results, started.elapsed())),
+            Err(e) => (false, format!("{} {}", "✗".red(), e)),
+        };
+        tally.record(passed);
+        println!("{} {}", Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true).dimmed(), status);
+
+        if settings.fail_after.is_some_and(|limit| tally.consecutive_failures >= limit) {
+            break MonitorStop::TooManyFailures;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(settings.interval.saturating_sub(started.elapsed())) => {},
+            _ = tokio::signal::ctrl_c() => break MonitorStop::Interrupted,
+        }
+    };
+
+    match stop {
+        MonitorStop::Interrupted => println!("\n{} {}", "Monitor stopped:".bold(), tally.summary()),
+        MonitorStop::TooManyFailures => println!(
+            "\n{} {} consecutive failed cycles; {}",
+            "Monitor stopped:".bold().red(),
+            tally.consecutive_failures,
+            tally.summary()
+        ),
+    }
+
+    (tally, stop)
+}
+
+/// One line per cycle: `✓ 3/3 passed (412ms)` or `✗ 2/3 passed (380ms), failing: orders.greq`
+fn format_cycle_status(results: &[ExecutionResult], elapsed: Duration) -> String {
+    let failing: Vec<&str> = results
+        .iter()
+        .filter(|result| !result.success)
+        .map(|result| {
+            Path::new(&result.file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(&result.file_path)
+        })
+        .collect();
+    let passed = results.len() - failing.len();
+
+    if failing.is_empty() {
+        format!("{} {}/{} passed ({}ms)", "✓".green(), passed, results.len(), elapsed.as_millis())
+    } else {
+        format!(
+            "{} {}/{} passed ({}ms), failing: {}",
+            "✗".red(),
+            passed,
+            results.len(),
+            elapsed.as_millis(),
+            failing.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_tracks_consecutive_failures() {
+        let mut tally = MonitorTally::default();
+
+        for passed in [true, false, false, true, false] {
+            tally.record(passed);
+        }
+
+        assert_eq!(tally.consecutive_failures, 1);
+        assert_eq!(tally.summary(), "5 cycles: 2 passed, 3 failed (40.0% passing)");
+        assert_eq!(MonitorTally::default().summary(), "0 cycles: 0 passed, 0 failed (0.0% passing)");
+    }
+
+    #[test]
+    fn test_cycle_status_lists_failing_files() {
+        let results = vec![ExecutionResult::for_file("checks/users.greq", true), ExecutionResult::for_file("checks/orders.greq", false)];
+
+        let status = format_cycle_status(&results, Duration::from_millis(412));
+
+        assert!(status.ends_with("1/2 passed (412ms), failing: orders.greq"), "{status}");
+    }
+}
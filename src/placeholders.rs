@@ -1,9 +1,12 @@
 use crate::models::Response;
 use crate::error::{GreqError, Result};
+use crate::grammar::method_is_valid;
+use crate::json_path::{json_value_to_string, navigate_json_path, JsonPathError};
 use regex::Regex;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fs;
 
 /// Replace placeholders in a string with values from dependency response or environment variables
 pub fn replace_placeholders(text: &str, dependency_response: &Response) -> Result<String> {
@@ -15,9 +18,19 @@ pub fn replace_placeholders_with_context(
     text: &str, 
     dependency_response: &Response, 
     file_path: &str, 
-    location: &str
+    location: &str,
 ) -> Result<String> {
-    let placeholder_regex = Regex::new(r"\$\(([\w\.\-\[\]]+)\)")?;
+    substitute_placeholders(text, dependency_response, file_path, location, false)
+}
+
+fn substitute_placeholders(
+    text: &str,
+    dependency_response: &Response,
+    file_path: &str,
+    location: &str,
+    show_warnings: bool,
+) -> Result<String> {
+    let placeholder_regex = Regex::new(r"\$\(([\w\.\-\[\]\*]+)\)")?;
     let mut result = text.to_string();
     
     for capture in placeholder_regex.captures_iter(text) {
@@ -27,7 +40,13 @@ pub fn replace_placeholders_with_context(
         let value = if placeholder_path.starts_with("environment.") {
             extract_environment_variable_with_context(placeholder_path, file_path, location)?
         } else {
-            extract_value_from_response_with_context(placeholder_path, dependency_response, file_path, location)?
+            let value = extract_value_from_response_with_context(placeholder_path, dependency_response, file_path, location)?;
+            if show_warnings {
+                if let Some(warning) = empty_value_warning(full_match, &value, file_path, location) {
+                    warn(&warning);
+                }
+            }
+            value
         };
         
         result = result.replace(full_match, &value);
@@ -36,6 +55,22 @@ pub fn replace_placeholders_with_context(
     Ok(result)
 }
 
+/// A dependency value that resolved to an empty string looks the same as the blank left by a failed
+/// dependency, so it is reported. Missing JSON fields are errors and never get here
+fn empty_value_warning(placeholder: &str, value: &str, file_path: &str, location: &str) -> Option<String> {
+    value.is_empty().then(|| format!(
+        "{}: {}: {} resolved to an empty string although the dependency succeeded",
+        file_path, location, placeholder
+    ))
+}
+
+/// Log a placeholder warning; test builds also keep it for the tests of the current thread
+fn warn(warning: &str) {
+    log::warn!("⚠ {}", warning);
+    #[cfg(test)]
+    tests::WARNINGS.with(|warnings| warnings.borrow_mut().push(warning.to_string()));
+}
+
 /// Extract environment variable value from placeholder path with context
 fn extract_environment_variable_with_context(
     path: &str, 
@@ -97,7 +132,7 @@ fn extract_value_from_response_with_context(
                 let header_name = &stripped.to_lowercase();
                 Ok(response.headers.get(header_name).cloned().unwrap_or_default())
             } else if let Some(json_path) = actual_path.strip_prefix("response-body.") {
-                extract_json_path_with_context(&response.body, json_path, file_path, location)
+                extract_json_path_with_context(response, json_path, file_path, location)
             } else {
                 Err(GreqError::Placeholder(format!("{}: {}: Unknown placeholder path: {actual_path}", file_path, location)))
             }
@@ -105,112 +140,26 @@ fn extract_value_from_response_with_context(
     }
 }
 
-/// Extract value from JSON using JSONPath-like syntax with context
+/// Extract value from the JSON response body using JSONPath-like syntax with context
 fn extract_json_path_with_context(
-    json_text: &str, 
+    response: &Response, 
     path: &str, 
     file_path: &str, 
     location: &str
 ) -> Result<String> {
-    let value: Value = serde_json::from_str(json_text)
-        .map_err(|_| GreqError::Placeholder(format!("{}: {}: Response body is not valid JSON", file_path, location)))?;
-    
-    let result = navigate_json_path_with_context(&value, path, file_path, location)?;
+    let value = response.json()
+        .ok_or_else(|| GreqError::Placeholder(format!("{}: {}: Response body is not valid JSON", file_path, location)))?;
     
-    match result {
-        Value::String(s) => Ok(s),
-        Value::Number(n) => Ok(n.to_string()),
-        Value::Bool(b) => Ok(b.to_string()),
-        Value::Null => Ok("null".to_string()),
-        _ => Ok(serde_json::to_string(&result)?),
-    }
-}
-
-/// Navigate through JSON using a simple path syntax with context
-fn navigate_json_path_with_context(
-    value: &Value, 
-    path: &str, 
-    file_path: &str, 
-    location: &str
-) -> Result<Value> {
-    let mut current = value;
-    let parts = parse_json_path(path)?;
-    
-    for part in parts {
-        match part {
-            PathPart::Property(key) => {
-                if let Value::Object(obj) = current {
-                    current = obj.get(&key)
-                        .ok_or_else(|| GreqError::Placeholder(format!("{}: {}: Property '{}' not found in JSON path '{}'. Please check the actual response.", file_path, location, key, path)))?;
-                } else {
-                    return Err(GreqError::Placeholder(format!("{}: {}: Cannot access property '{}' on non-object in JSON path '{}'", file_path, location, key, path)));
-                }
-            },
-            PathPart::Index(index) => {
-                if let Value::Array(arr) = current {
-                    current = arr.get(index)
-                        .ok_or_else(|| GreqError::Placeholder(format!("{}: {}: Array index {} out of bounds (length: {}) in JSON path '{}'", file_path, location, index, arr.len(), path)))?;
-                } else {
-                    return Err(GreqError::Placeholder(format!("{}: {}: Cannot access index {} on non-array in JSON path '{}'", file_path, location, index, path)));
-                }
-            },
-        }
-    }
+    let result = navigate_json_path(value, path).map_err(|e| match e {
+        // A placeholder stands for one value, so it cannot sweep an array
+        JsonPathError::UnexpectedWildcard { path } => GreqError::Placeholder(format!(
+            "{}: {}: Wildcard '[*]' in '{}' cannot be used in a placeholder; use an index such as [0]",
+            file_path, location, path
+        )),
+        e => GreqError::Placeholder(format!("{}: {}: {}", file_path, location, e)),
+    })?;
     
-    Ok(current.clone())
-}
-
-/// Represents a part of a JSON path
-#[derive(Debug, Clone)]
-enum PathPart {
-    Property(String),
-    Index(usize),
-}
-
-/// Parse a JSON path string into path parts
-fn parse_json_path(path: &str) -> Result<Vec<PathPart>> {
-    let mut parts = Vec::new();
-    let mut current = String::new();
-    let mut chars = path.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        match ch {
-            '.' => {
-                if !current.is_empty() {
-                    parts.push(PathPart::Property(current.clone()));
-                    current.clear();
-                }
-            },
-            '[' => {
-                if !current.is_empty() {
-                    parts.push(PathPart::Property(current.clone()));
-                    current.clear();
-                }
-                
-                // Parse array index
-                let mut index_str = String::new();
-                for ch in chars.by_ref() {
-                    if ch == ']' {
-                        break;
-                    }
-                    index_str.push(ch);
-                }
-                
-                let index: usize = index_str.parse()
-                    .map_err(|_| GreqError::Placeholder(format!("Invalid array index: {index_str}")))?;
-                parts.push(PathPart::Index(index));
-            },
-            _ => {
-                current.push(ch);
-            }
-        }
-    }
-    
-    if !current.is_empty() {
-        parts.push(PathPart::Property(current));
-    }
-    
-    Ok(parts)
+    Ok(json_value_to_string(result))
 }
 
 /// Replace placeholders in all text fields of a GreqFile
@@ -233,6 +182,208 @@ pub fn replace_placeholders_in_greq_file_with_optional_response(
     )
 }
 
+/// Resolve `$(environment.KEY)` placeholders from command line overrides before the process environment is consulted
+pub fn apply_variable_overrides(
+    greq_file: &mut crate::models::GreqFile,
+    overrides: &HashMap<String, String>,
+) {
+    if overrides.is_empty() {
+        return;
+    }
+
+    let replace = |text: &str| {
+        overrides.iter().fold(text.to_string(), |text, (name, value)| {
+            text.replace(&format!("$(environment.{name})"), value)
+        })
+    };
+
+    let method = replace(&greq_file.content.request_line.method);
+    if method != greq_file.content.request_line.method {
+        greq_file.content.request_line.method = method.trim().to_uppercase();
+    }
+    greq_file.content.request_line.uri = replace(&greq_file.content.request_line.uri);
+    for value in greq_file.content.headers.values_mut() {
+        *value = replace(value);
+    }
+    if let Some(body) = &greq_file.content.body {
+        greq_file.content.body = Some(replace(body));
+    }
+    for condition in greq_file.footer.conditions.iter_mut() {
+        condition.value = replace(&condition.value);
+    }
+    for value in greq_file.header.set_environment.values_mut() {
+        *value = replace(value);
+    }
+    for (_, value) in greq_file.header.form.iter_mut() {
+        *value = replace(value);
+    }
+}
+
+/// Insert the `{{> name}}` partials of header values and the body from the `includes-file` snippets.
+/// Runs before any `$(...)` placeholder, so snippets can contain placeholders; they are not expanded again
+pub fn expand_partials(greq_file: &mut crate::models::GreqFile) -> Result<()> {
+    let partial_regex = Regex::new(r"\{\{>\s*([\w\-.]+)\s*\}\}")?;
+    let content = &greq_file.content;
+    if !content.body.iter().chain(content.headers.values()).any(|text| partial_regex.is_match(text)) {
+        return Ok(());
+    }
+
+    let file_path = greq_file.file_path.clone();
+    let snippets = load_partials(&file_path, greq_file.header.includes_file.as_deref())?;
+    let expand = |text: &str, location: &str| -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut copied = 0;
+        for capture in partial_regex.captures_iter(text) {
+            let include = capture.get(0).expect("a match has a whole group");
+            let name = &capture[1];
+            let snippet = snippets.get(name).ok_or_else(|| {
+                let available: Vec<&str> = snippets.keys().map(String::as_str).collect();
+                GreqError::Placeholder(format!(
+                    "{}: {}: unknown partial '{}' (available: {})",
+                    file_path, location, name,
+                    if available.is_empty() { "none".to_string() } else { available.join(", ") }
+                ))
+            })?;
+            result.push_str(&text[copied..include.start()]);
+            result.push_str(snippet);
+            copied = include.end();
+        }
+        result.push_str(&text[copied..]);
+        Ok(result)
+    };
+
+    for (key, value) in greq_file.content.headers.iter_mut() {
+        *value = expand(value, &format!("header '{}'", key))?;
+    }
+    if let Some(body) = &greq_file.content.body {
+        greq_file.content.body = Some(expand(body, "request body")?);
+    }
+
+    Ok(())
+}
+
+/// Snippets of an `includes-file`: a JSON object whose string values are inserted as they are
+/// and whose other values are inserted as compact JSON
+fn load_partials(file_path: &str, includes_file: Option<&str>) -> Result<BTreeMap<String, String>> {
+    let includes_file = includes_file.ok_or_else(|| GreqError::Placeholder(format!(
+        "{file_path}: {{{{> name}}}} partials need an includes-file header"
+    )))?;
+    let text = fs::read_to_string(includes_file).map_err(|e| GreqError::Placeholder(format!(
+        "{file_path}: Cannot read includes-file '{includes_file}': {e}"
+    )))?;
+    let snippets: serde_json::Map<String, Value> = serde_json::from_str(&text).map_err(|e| GreqError::Placeholder(format!(
+        "{file_path}: includes-file '{includes_file}' must be a JSON object of named snippets: {e}"
+    )))?;
+
+    Ok(snippets.into_iter().map(|(name, value)| match value {
+        Value::String(text) => (name, text),
+        value => (name, value.to_string()),
+    }).collect())
+}
+
+/// Resolve `$(vars.<name>)` placeholders from the values extracted by files that ran earlier in this invocation
+pub fn apply_suite_variables(
+    greq_file: &mut crate::models::GreqFile,
+    variables: &HashMap<String, String>,
+) -> Result<()> {
+    let vars_regex = Regex::new(r"\$\(vars\.([\w\-]+)\)")?;
+    let file_path = greq_file.file_path.clone();
+    let replace = |text: &str, location: &str| -> Result<String> {
+        let mut result = text.to_string();
+        for capture in vars_regex.captures_iter(text) {
+            let name = &capture[1];
+            let value = variables.get(name).ok_or_else(|| GreqError::Placeholder(format!(
+                "{}: {}: $(vars.{}) is not set, no file that ran before this one extracted '{}' (failing files extract nothing)",
+                file_path, location, name, name
+            )))?;
+            result = result.replace(&capture[0], value);
+        }
+        Ok(result)
+    };
+
+    let method = replace(&greq_file.content.request_line.method, "request method")?;
+    set_resolved_method(greq_file, method)?;
+    greq_file.content.request_line.uri = replace(&greq_file.content.request_line.uri, "request URI")?;
+    for (key, value) in greq_file.content.headers.iter_mut() {
+        *value = replace(value, &format!("header '{}'", key))?;
+    }
+    if let Some(body) = &greq_file.content.body {
+        greq_file.content.body = Some(replace(body, "request body")?);
+    }
+    for (i, condition) in greq_file.footer.conditions.iter_mut().enumerate() {
+        condition.value = replace(&condition.value, &format!("condition {} value", i + 1))?;
+    }
+    for (name, value) in greq_file.header.set_environment.iter_mut() {
+        *value = replace(value, &format!("set-environment.{}", name))?;
+    }
+    for (name, value) in greq_file.header.form.iter_mut() {
+        *value = replace(value, &format!("form field '{}'", name))?;
+    }
+
+    Ok(())
+}
+
+/// Store a request method produced by placeholders, checking that it is still a method greq can send
+fn set_resolved_method(greq_file: &mut crate::models::GreqFile, method: String) -> Result<()> {
+    if method == greq_file.content.request_line.method {
+        return Ok(());
+    }
+    let method = method.trim().to_uppercase();
+    if !method_is_valid(&method) {
+        return Err(GreqError::Placeholder(format!(
+            "{}: request method: '{}' resolved to '{}', which is not a supported HTTP method",
+            greq_file.file_path, greq_file.content.request_line.method, method
+        )));
+    }
+    greq_file.content.request_line.method = method;
+    Ok(())
+}
+
+/// Resolve `$(environment.KEY)` placeholders in `extends` and `depends-on` before the referenced files are loaded.
+/// Only environment values (including command line overrides) exist at that point, so any other placeholder is an error
+pub fn replace_placeholders_in_file_references(
+    greq_file: &mut crate::models::GreqFile,
+    overrides: &HashMap<String, String>,
+) -> Result<()> {
+    let file_path = &greq_file.file_path;
+    let header = &mut greq_file.header;
+
+    for (property, reference) in [("extends", &mut header.extends), ("depends-on", &mut header.depends_on)] {
+        if let Some(reference) = reference {
+            *reference = resolve_file_reference(reference, property, file_path, overrides)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_file_reference(
+    reference: &str,
+    property: &str,
+    file_path: &str,
+    overrides: &HashMap<String, String>,
+) -> Result<String> {
+    let placeholder_regex = Regex::new(r"\$\(([^)]+)\)")?;
+    let mut result = reference.to_string();
+
+    for capture in placeholder_regex.captures_iter(reference) {
+        let path = &capture[1];
+        let value = match path.strip_prefix("environment.") {
+            Some(name) if overrides.contains_key(name) => overrides[name].clone(),
+            Some(_) => extract_environment_variable_with_context(path, file_path, property)?,
+            None => {
+                return Err(GreqError::Placeholder(format!(
+                    "{}: {}: Only $(environment.*) placeholders can be used here, '{}' is not available before any request runs",
+                    file_path, property, &capture[0]
+                )))
+            }
+        };
+        result = result.replace(&capture[0], &value);
+    }
+
+    Ok(result)
+}
+
 /// Replace dependency placeholders with empty string when dependency fails
 fn replace_dependency_placeholders_with_empty_string(
     text: &str,
@@ -356,6 +507,86 @@ fn validate_dependency_placeholders(
     Ok(())
 }
 
+/// Generate the value of a `$(random.*)` or `$(timestamp*)` placeholder
+fn generate_placeholder_value(path: &str, file_path: &str, location: &str) -> Result<String> {
+    match path {
+        "random.uuid" => Ok(uuid::Uuid::new_v4().to_string()),
+        "timestamp" => Ok(chrono::Utc::now().timestamp().to_string()),
+        "timestamp.iso" => Ok(chrono::Utc::now().to_rfc3339()),
+        _ => Err(GreqError::Placeholder(format!("{}: {}: Unknown generated placeholder: {path}", file_path, location))),
+    }
+}
+
+/// Replace request-local placeholders in a GreqFile:
+/// - generated values (`$(random.uuid)`, `$(timestamp)`, `$(timestamp.iso)`) resolve once per file,
+///   so the same id used in the URI also appears in the body
+/// - `$(self.method)`, `$(self.uri)` and `$(self.host)` resolve to the request being built
+fn replace_request_local_placeholders(greq_file: &mut crate::models::GreqFile) -> Result<()> {
+    let generated_regex = Regex::new(r"\$\(((?:random|timestamp)[\w\.\-]*)\)")?;
+    let self_regex = Regex::new(r"\$\((self\.[\w\-]+)\)")?;
+    let file_path = greq_file.file_path.clone();
+    let mut generated_values: HashMap<String, String> = HashMap::new();
+    
+    let mut replace_generated = |text: &str, location: &str| -> Result<String> {
+        let mut result = text.to_string();
+        for capture in generated_regex.captures_iter(text) {
+            let path = &capture[1];
+            if !generated_values.contains_key(path) {
+                let value = generate_placeholder_value(path, &file_path, location)?;
+                generated_values.insert(path.to_string(), value);
+            }
+            result = result.replace(&capture[0], &generated_values[path]);
+        }
+        Ok(result)
+    };
+    
+    greq_file.content.request_line.uri = replace_generated(&greq_file.content.request_line.uri, "request URI")?;
+    for (key, value) in greq_file.content.headers.iter_mut() {
+        *value = replace_generated(value, &format!("header '{}'", key))?;
+    }
+    if let Some(body) = &greq_file.content.body {
+        greq_file.content.body = Some(replace_generated(body, "request body")?);
+    }
+    for (name, value) in greq_file.header.form.iter_mut() {
+        *value = replace_generated(value, &format!("form field '{}'", name))?;
+    }
+    for (i, condition) in greq_file.footer.conditions.iter_mut().enumerate() {
+        condition.value = replace_generated(&condition.value, &format!("condition {} value", i + 1))?;
+    }
+    
+    // Self values are taken after generated values are resolved, so they match what is sent
+    let mut self_values = HashMap::new();
+    self_values.insert("self.method", greq_file.content.request_line.method.clone());
+    self_values.insert("self.uri", greq_file.content.request_line.uri.clone());
+    self_values.insert("self.host", greq_file.content.headers.get("host").cloned().unwrap_or_default());
+    
+    let replace_self = |text: &str, location: &str| -> Result<String> {
+        let mut result = text.to_string();
+        for capture in self_regex.captures_iter(text) {
+            let path = &capture[1];
+            let value = self_values.get(path)
+                .ok_or_else(|| GreqError::Placeholder(format!("{}: {}: Unknown self placeholder: {path}", file_path, location)))?;
+            result = result.replace(&capture[0], value);
+        }
+        Ok(result)
+    };
+    
+    for (key, value) in greq_file.content.headers.iter_mut() {
+        *value = replace_self(value, &format!("header '{}'", key))?;
+    }
+    if let Some(body) = &greq_file.content.body {
+        greq_file.content.body = Some(replace_self(body, "request body")?);
+    }
+    for (name, value) in greq_file.header.form.iter_mut() {
+        *value = replace_self(value, &format!("form field '{}'", name))?;
+    }
+    for (i, condition) in greq_file.footer.conditions.iter_mut().enumerate() {
+        condition.value = replace_self(&condition.value, &format!("condition {} value", i + 1))?;
+    }
+    
+    Ok(())
+}
+
 /// Enhanced version that handles dependency failures and shows warnings
 pub fn replace_placeholders_in_greq_file_with_dependency_handling(
     greq_file: &mut crate::models::GreqFile,
@@ -365,6 +596,9 @@ pub fn replace_placeholders_in_greq_file_with_dependency_handling(
     // Validate that dependency placeholders are only used when depends-on is provided
     validate_dependency_placeholders(greq_file)?;
     
+    // Resolve values of the request itself before dependency and environment placeholders
+    replace_request_local_placeholders(greq_file)?;
+    
     // Check if we should show warnings and if dependency failure with placeholders should warn
     let should_warn = greq_file.header.show_warnings 
         && greq_file.header.allow_dependency_failure 
@@ -374,9 +608,8 @@ pub fn replace_placeholders_in_greq_file_with_dependency_handling(
     // Create a dummy response if none provided (for environment-only placeholders)
     let dummy_response = Response {
         status_code: 200,
-        headers: HashMap::new(),
         body: "{}".to_string(),
-        latency: std::time::Duration::from_millis(0),
+        ..Response::default()
     };
     
     let response = dependency_response.unwrap_or(&dummy_response);
@@ -397,10 +630,17 @@ pub fn replace_placeholders_in_greq_file_with_dependency_handling(
             Ok(result)
         } else {
             // Normal replacement
-            replace_placeholders_with_context(text, response, file_path, location)
+            substitute_placeholders(text, response, file_path, location, greq_file.header.show_warnings)
         }
     };
     
+    // Replace in the method, which must still be a supported method once resolved
+    let method = replace_with_warning(
+        &greq_file.content.request_line.method,
+        "request method",
+        &mut placeholder_warning_shown,
+    )?;
+    
     // Replace in URI
     greq_file.content.request_line.uri = replace_with_warning(
         &greq_file.content.request_line.uri,
@@ -429,6 +669,18 @@ pub fn replace_placeholders_in_greq_file_with_dependency_handling(
         )?);
     }
     
+    // Replace in form field values
+    let mut updated_form = Vec::new();
+    for (name, value) in &greq_file.header.form {
+        let updated_value = replace_with_warning(
+            value,
+            &format!("form field '{}'", name),
+            &mut placeholder_warning_shown,
+        )?;
+        updated_form.push((name.clone(), updated_value));
+    }
+    greq_file.header.form = updated_form;
+    
     // Replace in condition values
     for (i, condition) in greq_file.footer.conditions.iter_mut().enumerate() {
         condition.value = replace_with_warning(
@@ -438,13 +690,23 @@ pub fn replace_placeholders_in_greq_file_with_dependency_handling(
         )?;
     }
     
-    Ok(())
+    set_resolved_method(greq_file, method)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
     use std::time::Duration;
+
+    thread_local! {
+        /// Placeholder warnings logged by the current test
+        pub(super) static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn logged_warnings() -> Vec<String> {
+        WARNINGS.with(|warnings| warnings.borrow().clone())
+    }
     
     fn create_test_response() -> Response {
         let mut headers = HashMap::new();
@@ -455,6 +717,7 @@ mod tests {
             headers,
             body: r#"{"id": 123, "name": "test", "items": [{"id": 1}, {"id": 2}]}"#.to_string(),
             latency: Duration::from_millis(150),
+            ..Response::default()
         }
     }
     
@@ -489,6 +752,13 @@ mod tests {
         let result = replace_placeholders(text, &response).unwrap();
         assert_eq!(result, "First item: 1");
     }
+
+    #[test]
+    fn test_wildcard_is_rejected_in_placeholder() {
+        let response = create_test_response();
+        let error = replace_placeholders("$(dependency.response-body.items[*].id)", &response).unwrap_err();
+        assert!(error.to_string().contains("Wildcard '[*]' in 'items[*].id' cannot be used in a placeholder; use an index"), "{error}");
+    }
     
     #[test]
     fn test_replace_environment_variable_placeholder() {
@@ -559,6 +829,8 @@ mod tests {
     
     #[test]
     fn test_parse_json_path() {
+        use crate::json_path::{parse_json_path, PathPart};
+        
         let parts = parse_json_path("items[0].id").unwrap();
         assert_eq!(parts.len(), 3);
         
@@ -687,6 +959,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -740,6 +1013,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -766,6 +1040,44 @@ mod tests {
         // No warning should be logged (we can't easily test log output, but function should complete without error)
     }
 
+    #[test]
+    fn test_empty_field_warns_and_missing_field_fails() {
+        use crate::models::{Content, Footer, GreqFile, Header};
+
+        let response = Response {
+            body: r#"{"token": "", "user": {"id": 7}}"#.to_string(),
+            ..Response::default()
+        };
+        let greq_file = |authorization: &str| {
+            let mut content = Content::default();
+            content.headers.insert("authorization".to_string(), authorization.to_string());
+            GreqFile {
+                header: Header {
+                    depends_on: Some("login.greq".to_string()),
+                    show_warnings: true,
+                    ..Header::default()
+                },
+                content,
+                footer: Footer::default(),
+                file_path: "empty-token.greq".to_string(),
+            }
+        };
+
+        let mut file = greq_file("Bearer $(dependency.response-body.token)");
+        replace_placeholders_in_greq_file_with_dependency_handling(&mut file, Some(&response), false).unwrap();
+        assert_eq!(file.content.headers["authorization"], "Bearer ");
+        assert!(logged_warnings().contains(&"empty-token.greq: header 'authorization': $(dependency.response-body.token) resolved to an empty string although the dependency succeeded".to_string()));
+
+        let mut file = greq_file("Bearer $(dependency.response-body.user.id)");
+        replace_placeholders_in_greq_file_with_dependency_handling(&mut file, Some(&response), false).unwrap();
+        assert_eq!(file.content.headers["authorization"], "Bearer 7");
+        assert!(!logged_warnings().iter().any(|warning| warning.contains("user.id")));
+
+        let mut file = greq_file("Bearer $(dependency.response-body.missing)");
+        let error = replace_placeholders_in_greq_file_with_dependency_handling(&mut file, Some(&response), false).unwrap_err();
+        assert!(error.to_string().contains("empty-token.greq: header 'authorization': Property 'missing' not found"), "{error}");
+    }
+
     #[test]
     fn test_dep_prefix_replacement() {
         let content = "Authorization: Bearer $(dep.response-body.token)";
@@ -799,6 +1111,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -843,6 +1156,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -883,6 +1197,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -907,6 +1222,7 @@ mod tests {
             headers: HashMap::new(),
             body: r#"{"token": "abc123"}"#.to_string(),
             latency: std::time::Duration::from_millis(100),
+            ..Response::default()
         };
 
         let result = replace_placeholders_in_greq_file_with_dependency_handling(&mut greq_file, Some(&dummy_response), false);
@@ -1008,6 +1324,7 @@ mod tests {
                 execute_before: None,
                 execute_after: None,
                 set_environment: HashMap::new(),
+                ..Header::default()
             },
             content: Content {
                 request_line: RequestLine {
@@ -1022,13 +1339,17 @@ mod tests {
                 conditions: vec![
                     Condition {
                         is_or: false,
+                        is_and: false,
                         is_not: false,
                         key: ConditionKey::StatusCode,
                         operator: Operator::Equals,
                         case_sensitive: false,
                         value: "$(dependency.status-code)".to_string(),
+                        name: None,
+                        environments: Vec::new(),
                     },
                 ],
+                ..Footer::default()
             },
             file_path: "test-file.greq".to_string(),
         };
@@ -1039,5 +1360,117 @@ mod tests {
         let error_msg = result.unwrap_err().to_string();
         assert!(error_msg.contains("condition 1 value: Dependency placeholder 'dependency.status-code' found but no 'depends-on' is defined"));
     }
-}
 
+    #[test]
+    fn test_random_uuid_resolves_once_per_file() {
+        use crate::models::{GreqFile, Header, Content, RequestLine, Footer};
+
+        let mut greq_file = GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: "PUT".to_string(),
+                    uri: "/orders/$(random.uuid)".to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                headers: HashMap::new(),
+                body: Some(r#"{"id": "$(random.uuid)", "method": "$(self.method)", "path": "$(self.uri)"}"#.to_string()),
+            },
+            footer: Footer::default(),
+            file_path: "test-file.greq".to_string(),
+        };
+
+        replace_placeholders_in_greq_file_with_optional_response(&mut greq_file, None).unwrap();
+
+        let uri = &greq_file.content.request_line.uri;
+        let id = uri.strip_prefix("/orders/").unwrap();
+        assert_eq!(id.len(), 36);
+        assert_eq!(
+            greq_file.content.body.unwrap(),
+            format!(r#"{{"id": "{}", "method": "PUT", "path": "{}"}}"#, id, uri)
+        );
+    }
+
+    #[test]
+    fn test_unknown_self_placeholder() {
+        use crate::models::{GreqFile, Header, Content, Footer};
+
+        let mut greq_file = GreqFile {
+            header: Header::default(),
+            content: Content {
+                body: Some("$(self.nonexistent)".to_string()),
+                ..Content::default()
+            },
+            footer: Footer::default(),
+            file_path: "test-file.greq".to_string(),
+        };
+
+        let result = replace_placeholders_in_greq_file_with_optional_response(&mut greq_file, None);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Unknown self placeholder: self.nonexistent"));
+    }
+
+    #[test]
+    fn test_method_from_placeholder() {
+        use crate::models::{GreqFile, Header, Content, RequestLine, Footer};
+
+        let template = GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: "$(environment.METHOD)".to_string(),
+                    uri: "/items".to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                ..Content::default()
+            },
+            footer: Footer::default(),
+            file_path: "items.greq".to_string(),
+        };
+        let resolve = |method: &str| {
+            let mut greq_file = template.clone();
+            apply_variable_overrides(&mut greq_file, &HashMap::from([("METHOD".to_string(), method.to_string())]));
+            replace_placeholders_in_greq_file_with_optional_response(&mut greq_file, None).map(|_| greq_file)
+        };
+
+        assert_eq!(resolve("get").unwrap().content.request_line.method, "GET");
+        assert_eq!(resolve("POST").unwrap().content.request_line.method, "POST");
+
+        let mut greq_file = template.clone();
+        greq_file.content.request_line.method = "$(vars.verb)".to_string();
+        apply_suite_variables(&mut greq_file, &HashMap::from([("verb".to_string(), "patch".to_string())])).unwrap();
+        assert_eq!(greq_file.content.request_line.method, "PATCH");
+
+        let mut greq_file = template.clone();
+        greq_file.content.request_line.method = "$(vars.verb)".to_string();
+        let error = apply_suite_variables(&mut greq_file, &HashMap::from([("verb".to_string(), "FETCH".to_string())])).unwrap_err();
+        assert!(error.to_string().contains("request method: '$(vars.verb)' resolved to 'FETCH', which is not a supported HTTP method"), "{error}");
+    }
+
+    #[test]
+    fn test_expand_partials() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("partials.json"),
+            r#"{"address": {"city": "Oslo", "zip": "0150"}, "auth": "Bearer $(environment.TOKEN)"}"#,
+        ).unwrap();
+        let file_path = dir.path().join("create.greq");
+        let write_file = |body: &str| {
+            std::fs::write(
+                &file_path,
+                format!("project: users\nincludes-file: partials.json\n====\nPOST /users\nhost: example.com\nauthorization: {{{{> auth}}}}\n\n{body}"),
+            ).unwrap();
+            crate::parser::parse_greq_file(&file_path).unwrap()
+        };
+
+        let mut greq_file = write_file(r#"{"name": "Ann", "address": {{> address}}}"#);
+        expand_partials(&mut greq_file).unwrap();
+        assert_eq!(greq_file.content.body.as_deref(), Some(r#"{"name": "Ann", "address": {"city":"Oslo","zip":"0150"}}"#));
+        assert_eq!(greq_file.content.headers["authorization"], "Bearer $(environment.TOKEN)");
+
+        let mut greq_file = write_file(r#"{"address": {{> adress}}}"#);
+        let error = expand_partials(&mut greq_file).unwrap_err();
+        assert!(error.to_string().contains("request body: unknown partial 'adress' (available: address, auth)"), "{error}");
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::{GreqError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Config file holding the `[profiles.<name>]` sections selected with `--profile`
+pub const CONFIG_FILE_NAME: &str = "greq.toml";
+
+/// The config file to read profiles from: `greq.toml` in the current directory, then in the home directory
+pub fn find_config_file(current_dir: &Path, home_dir: Option<&Path>) -> Option<PathBuf> {
+    std::iter::once(current_dir)
+        .chain(home_dir)
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Command line options supplied by a profile. Keys are long option names without the dashes:
+/// strings and numbers become `--key value`, `true` becomes `--key` and arrays repeat the option
+pub fn load_profile_args(config_path: &Path, name: &str) -> Result<Vec<String>> {
+    let source = config_path.display();
+    let config: toml::Table = fs::read_to_string(config_path)?
+        .parse()
+        .map_err(|e| GreqError::Parse(format!("{source}: {e}"), None))?;
+
+    let profiles = config.get("profiles").and_then(Value::as_table);
+    let profile = profiles
+        .and_then(|profiles| profiles.get(name))
+        .ok_or_else(|| {
+            let available: Vec<&str> = profiles.into_iter().flat_map(|profiles| profiles.keys()).map(String::as_str).collect();
+            GreqError::Validation(format!(
+                "{source}: profile '{name}' not found (available: {})",
+                if available.is_empty() { "none".to_string() } else { available.join(", ") }
+            ))
+        })?
+        .as_table()
+        .ok_or_else(|| GreqError::Parse(format!("{source}: profiles.{name} must be a table of options"), None))?;
+
+    let mut args = Vec::new();
+    for (key, value) in profile {
+        if key == "profile" {
+            return Err(GreqError::Validation(format!("{source}: profile '{name}' cannot select another profile")));
+        }
+        let option = format!("--{key}");
+        match value {
+            Value::Boolean(true) => args.push(option),
+            Value::Boolean(false) => {},
+            Value::Array(items) => {
+                for item in items {
+                    args.push(option.clone());
+                    args.push(option_value(item, &source.to_string(), name, key)?);
+                }
+            },
+            value => {
+                args.push(option);
+                args.push(option_value(value, &source.to_string(), name, key)?);
+            },
+        }
+    }
+
+    Ok(args)
+}
+
+/// The command line form of a single profile value
+fn option_value(value: &Value, source: &str, name: &str, key: &str) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Integer(number) => Ok(number.to_string()),
+        Value::Float(number) => Ok(number.to_string()),
+        _ => Err(GreqError::Parse(format!(
+            "{source}: profiles.{name}.{key} must be a string, number, boolean or an array of them"
+        ), None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_profile_args() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &config,
+            r#"
+[profiles.staging]
+base-url = "https://staging.example.com"
+rate = 2.5
+no-retry = true
+verbose = false
+set = ["TOKEN=abc", "REGION=eu"]
+
+[profiles.ci]
+junit = "report.xml"
+"#,
+        )
+        .unwrap();
+
+        let args = load_profile_args(&config, "staging").unwrap();
+        assert_eq!(
+            args,
+            ["--base-url", "https://staging.example.com", "--no-retry", "--rate", "2.5", "--set", "TOKEN=abc", "--set", "REGION=eu"]
+        );
+
+        let error = load_profile_args(&config, "prod").unwrap_err();
+        assert!(error.to_string().contains("profile 'prod' not found (available: ci, staging)"), "{error}");
+    }
+
+    #[test]
+    fn test_find_config_file() {
+        let current = tempdir().unwrap();
+        let home = tempdir().unwrap();
+        assert_eq!(find_config_file(current.path(), Some(home.path())), None);
+
+        fs::write(home.path().join(CONFIG_FILE_NAME), "").unwrap();
+        assert_eq!(find_config_file(current.path(), Some(home.path())), Some(home.path().join(CONFIG_FILE_NAME)));
+
+        fs::write(current.path().join(CONFIG_FILE_NAME), "").unwrap();
+        assert_eq!(find_config_file(current.path(), Some(home.path())), Some(current.path().join(CONFIG_FILE_NAME)));
+    }
+}
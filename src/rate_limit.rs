@@ -0,0 +1,80 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Spaces outgoing requests evenly to stay under `--rate` requests per second.
+/// Clones share the same schedule so one limiter gates every parallel execution
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Arc<Mutex<Option<Instant>>>,
+}
+
+impl RateLimiter {
+    /// A limiter allowing `rate` requests per second; fractions such as 0.5 are allowed
+    pub fn per_second(rate: f64) -> Self {
+        Self::with_interval(Duration::from_secs_f64(1.0 / rate))
+    }
+
+    fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Parse the `--rate` value
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        let rate = match value.parse::<f64>() {
+            Ok(rate) if rate.is_finite() && rate > 0.0 => rate,
+            _ => return Err(format!("expected a positive number of requests per second, got '{value}'")),
+        };
+        // Rates close to zero space requests further apart than a Duration can hold
+        Duration::try_from_secs_f64(1.0 / rate)
+            .map(Self::with_interval)
+            .map_err(|_| format!("rate '{value}' is too low, requests would never be sent"))
+    }
+
+    /// Wait for the next free slot; the first request goes out immediately
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |next| next.max(now));
+            *next_slot = Some(slot + self.interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_are_spaced() {
+        let limiter = RateLimiter::per_second(4.0);
+        let started = Instant::now();
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(990), "{:?}", started.elapsed());
+    }
+
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(RateLimiter::parse("0.5").unwrap().interval, Duration::from_secs(2));
+        assert!(RateLimiter::parse("0").is_err());
+        assert!(RateLimiter::parse("fast").is_err());
+        assert!(RateLimiter::parse("1e-20").unwrap_err().contains("too low"));
+    }
+}
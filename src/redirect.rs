@@ -0,0 +1,31 @@
+use reqwest::redirect::Policy;
+use std::sync::{Arc, Mutex};
+
+/// Most redirects followed for one request, the same limit as the client's default policy
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Records the URLs a request is redirected to, for `redirect-count` and `final-url`.
+/// Added to a client as its redirect policy; clones share the recorded hops
+#[derive(Debug, Clone, Default)]
+pub struct RedirectChain {
+    hops: Arc<Mutex<Vec<String>>>,
+}
+
+impl RedirectChain {
+    /// A policy following up to `MAX_REDIRECTS` redirects and recording each one
+    pub fn policy(&self) -> Policy {
+        let hops = self.hops.clone();
+        Policy::custom(move |attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                return attempt.error(format!("more than {MAX_REDIRECTS} redirects"));
+            }
+            hops.lock().unwrap_or_else(|e| e.into_inner()).push(attempt.url().to_string());
+            attempt.follow()
+        })
+    }
+
+    /// Take the hops recorded since the last call, in the order they were followed
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.hops.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::error::Result;
+use crate::models::GreqFile;
+use chrono::{SecondsFormat, Utc};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Append-only log of executed requests for `--log-requests`, one line per request:
+/// `timestamp file method url status latency`. Clones share the file so parallel
+/// executions never interleave within a line
+#[derive(Debug, Clone)]
+pub struct RequestLog {
+    file: Arc<Mutex<File>>,
+}
+
+impl RequestLog {
+    /// Open the log for appending, creating it when missing
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Arc::new(Mutex::new(file)) })
+    }
+
+    /// Append the line of a request; `status` is `None` when no response arrived.
+    /// Write errors only produce a warning so logging never fails a run
+    pub fn record(&self, greq_file: &GreqFile, status: Option<u16>, latency: Option<Duration>) {
+        let line = format_line(&Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true), greq_file, status, latency);
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.flush()) {
+            log::warn!("Failed to write the request log: {}", e);
+        }
+    }
+}
+
+/// One log line; a missing status or latency is written as `-`
+fn format_line(timestamp: &str, greq_file: &GreqFile, status: Option<u16>, latency: Option<Duration>) -> String {
+    format!(
+        "{} {} {} {} {} {}\n",
+        timestamp,
+        greq_file.file_path,
+        greq_file.content.request_line.method,
+        greq_file.request_url(),
+        status.map_or("-".to_string(), |status| status.to_string()),
+        latency.map_or("-".to_string(), |latency| format!("{}ms", latency.as_millis())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Content, Footer, Header, RequestLine};
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn greq_file() -> GreqFile {
+        GreqFile {
+            header: Header::default(),
+            content: Content {
+                request_line: RequestLine {
+                    method: "GET".to_string(),
+                    uri: "/users?page=2".to_string(),
+                    version: "HTTP/1.1".to_string(),
+                },
+                headers: HashMap::from([("host".to_string(), "api.example.com".to_string())]),
+                body: None,
+            },
+            footer: Footer::default(),
+            file_path: "tests/users.greq".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_request_log_appends_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("requests.log");
+        std::fs::write(&path, "earlier line\n").unwrap();
+
+        let log = RequestLog::open(&path).unwrap();
+        log.record(&greq_file(), Some(200), Some(Duration::from_millis(42)));
+        log.clone().record(&greq_file(), None, None);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "earlier line");
+        assert!(lines[1].ends_with(" tests/users.greq GET https://api.example.com/users?page=2 200 42ms"), "{}", lines[1]);
+        assert!(lines[2].ends_with(" tests/users.greq GET https://api.example.com/users?page=2 - -"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn test_request_url_follows_the_protocol() {
+        let mut greq_file = greq_file();
+        greq_file.header.is_websocket = true;
+        assert_eq!(greq_file.request_url(), "wss://api.example.com/users?page=2");
+        greq_file.header.is_http = true;
+        assert_eq!(greq_file.request_url(), "ws://api.example.com/users?page=2");
+    }
+}
@@ -0,0 +1,74 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Measures how long the client takes to open a connection (DNS, TCP and TLS handshake).
+/// Added to a client as a connector layer; clones share the measurement
+#[derive(Debug, Clone, Default)]
+pub struct ConnectTimer {
+    elapsed: Arc<Mutex<Option<Duration>>>,
+}
+
+impl ConnectTimer {
+    /// Take the last measurement; `None` when no connection was opened since the last call,
+    /// e.g. because a retry reused the pooled connection
+    pub fn take(&self) -> Option<Duration> {
+        self.elapsed.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    fn record(&self, elapsed: Duration) {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner()) = Some(elapsed);
+    }
+}
+
+impl<S> Layer<S> for ConnectTimer {
+    type Service = TimedConnect<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimedConnect {
+            inner,
+            timer: self.clone(),
+        }
+    }
+}
+
+/// Connector wrapped by `ConnectTimer`
+#[derive(Debug, Clone)]
+pub struct TimedConnect<S> {
+    inner: S,
+    timer: ConnectTimer,
+}
+
+impl<S, R> Service<R> for TimedConnect<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let started = Instant::now();
+        let timer = self.timer.clone();
+        let connecting = self.inner.call(request);
+
+        Box::pin(async move {
+            let connection = connecting.await;
+            if connection.is_ok() {
+                timer.record(started.elapsed());
+            }
+            connection
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::{GreqError, Result};
+use crate::models::Header;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{Resumption, WebPkiServerVerifier};
+use rustls::crypto::ring;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+/// Records the TLS protocol version of the handshakes made with `client_config`, for `tls.version`.
+/// native-tls does not report the version, so files asserting it are sent through rustls instead;
+/// clones share the recorded version
+#[derive(Debug, Clone, Default)]
+pub struct TlsVersionRecorder {
+    version: Arc<Mutex<Option<&'static str>>>,
+}
+
+impl TlsVersionRecorder {
+    /// A rustls configuration verifying the server against the system CA certificates, presenting the
+    /// `client-cert`/`client-key` of the header and recording the version of each full handshake
+    pub fn client_config(&self, header: &Header) -> Result<ClientConfig> {
+        let provider = Arc::new(ring::default_provider());
+        let verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(system_roots()?), provider.clone())
+            .build()
+            .map_err(|e| GreqError::Validation(format!("tls.version: cannot verify server certificates: {e}")))?;
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| GreqError::Validation(format!("tls.version: {e}")))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(RecordingVerifier { inner: verifier, version: self.version.clone() }));
+
+        let mut config = match (&header.client_cert, &header.client_key, &header.client_identity) {
+            (_, _, Some(_)) => return Err(GreqError::Validation(
+                "tls.version cannot be combined with client-identity; use client-cert and client-key".to_string(),
+            )),
+            (Some(cert_path), Some(key_path), None) => {
+                let invalid = |e: String| GreqError::Validation(format!(
+                    "Invalid client certificate '{cert_path}' / key '{key_path}': {e}"
+                ));
+                let chain = CertificateDer::pem_slice_iter(&read_file(cert_path, "client-cert")?)
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| invalid(e.to_string()))?;
+                let key = PrivateKeyDer::from_pem_slice(&read_file(key_path, "client-key")?)
+                    .map_err(|e| invalid(e.to_string()))?;
+                builder.with_client_auth_cert(chain, key).map_err(|e| invalid(e.to_string()))?
+            }
+            _ => builder.with_no_client_auth(),
+        };
+        // A resumed session skips the signature the version is read from
+        config.resumption = Resumption::disabled();
+        Ok(config)
+    }
+
+    /// The version of the latest handshake, `1.2` or `1.3`; `None` when no handshake was made
+    pub fn last(&self) -> Option<String> {
+        self.version.lock().unwrap_or_else(|e| e.into_inner()).map(str::to_string)
+    }
+}
+
+/// Verifies like `WebPkiServerVerifier`, noting which protocol's signature check the handshake ran
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    version: Arc<Mutex<Option<&'static str>>>,
+}
+
+impl RecordingVerifier {
+    fn record(&self, version: &'static str) {
+        *self.version.lock().unwrap_or_else(|e| e.into_inner()) = Some(version);
+    }
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.record("1.2");
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.record("1.3");
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// The CA certificates OpenSSL would trust: `SSL_CERT_FILE`/`SSL_CERT_DIR`, else the system bundle
+fn system_roots() -> Result<RootCertStore> {
+    let probe = openssl_probe::probe();
+    let mut pem_files = Vec::new();
+    pem_files.extend(probe.cert_file);
+    if let Some(dir) = probe.cert_dir.and_then(|dir| fs::read_dir(dir).ok()) {
+        pem_files.extend(dir.flatten().map(|entry| entry.path()).filter(|path| path.is_file()));
+    }
+
+    let mut roots = RootCertStore::empty();
+    for path in pem_files {
+        if let Ok(pem) = fs::read(&path) {
+            roots.add_parsable_certificates(CertificateDer::pem_slice_iter(&pem).flatten());
+        }
+    }
+    if roots.is_empty() {
+        return Err(GreqError::Validation(
+            "tls.version: no CA certificates found, set SSL_CERT_FILE to a PEM bundle".to_string(),
+        ));
+    }
+    Ok(roots)
+}
+
+fn read_file(path: &str, property: &str) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| GreqError::Validation(format!("Cannot read {property} file '{path}': {e}")))
+}